@@ -78,6 +78,12 @@ fn main() {
             let _ = xcode::writer::serializer::build(&p);
         });
 
+        // Object-model path: inflating into XcodeProject and writing back out
+        let project_rt_med = bench(|| {
+            let project = xcode::project::XcodeProject::from_plist(&content).unwrap();
+            let _ = project.to_pbxproj();
+        });
+
         println!("─ {} ({}) ─", fixture, size);
         println!(
             "  Lex:        {:>7.3} ms  ({:.0} MB/s)",
@@ -100,6 +106,10 @@ fn main() {
             json_deser_med,
         );
         println!("  JSON→build: {:>7.3} ms  (serde deser + build)", json_deser_build_med,);
+        println!(
+            "  Project RT: {:>7.3} ms  (XcodeProject::from_plist + to_pbxproj)",
+            project_rt_med,
+        );
         println!();
     }
 }
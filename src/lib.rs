@@ -34,7 +34,7 @@ mod wasm_bindings {
     pub fn build(project: JsValue) -> Result<String, JsError> {
         let plist: crate::types::PlistValue<'static> =
             serde_wasm_bindgen::from_value(project).map_err(|e| JsError::new(&e.to_string()))?;
-        Ok(crate::writer::serializer::build(&plist))
+        crate::writer::serializer::try_build(&plist).map_err(|e| JsError::new(&e))
     }
 
     /// Parse and immediately re-serialize a .pbxproj string.
@@ -104,6 +104,25 @@ mod wasm_bindings {
             self.inner.main_group_uuid()
         }
 
+        // ── Paths ────────────────────────────────────────────────
+
+        /// Set a virtual project root (the directory containing `*.xcodeproj`)
+        /// for path resolution, since projects parsed from a string have no file path.
+        #[wasm_bindgen(js_name = "setBasePath")]
+        pub fn set_base_path(&mut self, path: &str) {
+            self.inner.set_base_path(path)
+        }
+
+        #[wasm_bindgen(js_name = "getProjectRoot")]
+        pub fn get_project_root(&self) -> Option<String> {
+            self.inner.get_project_root()
+        }
+
+        #[wasm_bindgen(js_name = "fullPathOf")]
+        pub fn full_path_of(&self, uuid: &str) -> Option<String> {
+            self.inner.full_path_of(uuid)
+        }
+
         // ── Targets ──────────────────────────────────────────────
 
         #[wasm_bindgen(js_name = "getNativeTargets")]
@@ -166,6 +185,16 @@ mod wasm_bindings {
             self.inner.remove_build_setting(target_uuid, key)
         }
 
+        #[wasm_bindgen(js_name = "getDefaultConfiguration")]
+        pub fn get_default_configuration(&self, config_list_uuid: &str) -> Result<JsValue, JsError> {
+            match self.inner.get_default_configuration(config_list_uuid) {
+                Some(config) => crate::types::PlistValue::Object(config.to_plist())
+                    .serialize(&serializer())
+                    .map_err(|e| JsError::new(&e.to_string())),
+                None => Ok(JsValue::NULL),
+            }
+        }
+
         // ── Files & groups ───────────────────────────────────────
 
         #[wasm_bindgen(js_name = "addFile")]
@@ -190,6 +219,13 @@ mod wasm_bindings {
             self.inner.ensure_build_phase(target_uuid, phase_isa)
         }
 
+        #[wasm_bindgen(js_name = "getBuildPhase")]
+        pub fn get_build_phase(&self, target_uuid: &str, phase_isa: &str) -> Option<String> {
+            self.inner
+                .find_build_phase(target_uuid, phase_isa)
+                .map(|phase| phase.uuid.clone())
+        }
+
         #[wasm_bindgen(js_name = "addBuildFile")]
         pub fn add_build_file(&mut self, phase_uuid: &str, file_ref_uuid: &str) -> Option<String> {
             self.inner.add_build_file(phase_uuid, file_ref_uuid)
@@ -213,8 +249,17 @@ mod wasm_bindings {
         }
 
         #[wasm_bindgen(js_name = "embedExtension")]
-        pub fn embed_extension(&mut self, host: &str, extension: &str) -> Option<String> {
-            self.inner.embed_extension(host, extension)
+        pub fn embed_extension(&mut self, host: &str, extension: &str) -> Result<JsValue, JsError> {
+            match self.inner.embed_extension(host, extension) {
+                Some(e) => serde_json::json!({
+                    "phaseUuid": e.phase_uuid,
+                    "buildFileUuid": e.build_file_uuid,
+                    "dependencyUuid": e.dependency_uuid,
+                })
+                .serialize(&serializer())
+                .map_err(|e| JsError::new(&e.to_string())),
+                None => Ok(JsValue::NULL),
+            }
         }
 
         #[wasm_bindgen(js_name = "addFileSystemSyncGroup")]
@@ -229,6 +274,21 @@ mod wasm_bindings {
 
         // ── Generic access ───────────────────────────────────────
 
+        #[wasm_bindgen(js_name = "hasObject")]
+        pub fn has_object(&self, uuid: &str) -> bool {
+            self.inner.contains(uuid)
+        }
+
+        #[wasm_bindgen(js_name = "objectCount")]
+        pub fn object_count(&self) -> usize {
+            self.inner.object_count()
+        }
+
+        #[wasm_bindgen(js_name = "isEmpty")]
+        pub fn is_empty(&self) -> bool {
+            self.inner.is_empty()
+        }
+
         #[wasm_bindgen(js_name = "getObjectProperty")]
         pub fn get_object_property(&self, uuid: &str, key: &str) -> Option<String> {
             self.inner.get_object_property(uuid, key)
@@ -239,6 +299,13 @@ mod wasm_bindings {
             self.inner.set_object_property(uuid, key, value)
         }
 
+        #[wasm_bindgen(js_name = "setObjectValue")]
+        pub fn set_object_value(&mut self, uuid: &str, key: &str, value: JsValue) -> Result<bool, JsError> {
+            let value: crate::types::PlistValue<'static> =
+                serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&e.to_string()))?;
+            Ok(self.inner.set_object_value(uuid, key, value))
+        }
+
         #[wasm_bindgen(js_name = "findObjectsByIsa")]
         pub fn find_objects_by_isa(&self, isa: &str) -> Vec<String> {
             self.inner.find_objects_by_isa(isa)
@@ -261,6 +328,8 @@ mod wasm_bindings {
                             "referrerIsa": o.referrer_isa,
                             "property": o.property,
                             "orphanUuid": o.orphan_uuid,
+                            "inArray": o.in_array,
+                            "suggestedAction": o.suggested_action().as_str(),
                         })
                     })
                     .collect::<Vec<_>>(),
@@ -323,7 +392,7 @@ mod napi_bindings {
     pub fn build(project: serde_json::Value) -> Result<String> {
         let plist: crate::types::PlistValue<'static> =
             serde_json::from_value(project).map_err(|e| Error::from_reason(e.to_string()))?;
-        Ok(crate::writer::serializer::build(&plist))
+        crate::writer::serializer::try_build(&plist).map_err(Error::from_reason)
     }
 
     /// Serialize a JSON string back to .pbxproj format.
@@ -333,7 +402,7 @@ mod napi_bindings {
     pub fn build_from_json(json: String) -> Result<String> {
         let plist: crate::types::PlistValue<'static> =
             serde_json::from_str(&json).map_err(|e| Error::from_reason(e.to_string()))?;
-        Ok(crate::writer::serializer::build(&plist))
+        crate::writer::serializer::try_build(&plist).map_err(Error::from_reason)
     }
 
     /// Parse and immediately re-serialize a .pbxproj string.
@@ -386,6 +455,14 @@ mod napi_bindings {
             plist_to_napi(&env, plist)
         }
 
+        /// Serialize the project to a JSON string, optionally pretty-printed.
+        #[napi(js_name = "toJSONString")]
+        pub fn to_json_string(&self, pretty: Option<bool>) -> Result<String> {
+            self.inner
+                .to_json_string(pretty.unwrap_or(false))
+                .map_err(Error::from_reason)
+        }
+
         /// Serialize the project back to .pbxproj format.
         #[napi(js_name = "toBuild")]
         pub fn to_build(&self) -> String {
@@ -404,6 +481,24 @@ mod napi_bindings {
             self.inner.file_path().map(|s| s.to_string())
         }
 
+        /// Override the project root (the directory containing `*.xcodeproj`) used for path resolution.
+        #[napi]
+        pub fn set_base_path(&mut self, path: String) {
+            self.inner.set_base_path(&path)
+        }
+
+        /// Get the project root directory (parent of `*.xcodeproj`).
+        #[napi]
+        pub fn get_project_root(&self) -> Option<String> {
+            self.inner.get_project_root()
+        }
+
+        /// Resolve the filesystem path of a file reference or group.
+        #[napi]
+        pub fn full_path_of(&self, uuid: String) -> Option<String> {
+            self.inner.full_path_of(&uuid)
+        }
+
         /// Get the archive version.
         #[napi(getter)]
         pub fn archive_version(&self) -> i64 {
@@ -431,6 +526,16 @@ mod napi_bindings {
             }
         }
 
+        /// Get the default XCBuildConfiguration of a configuration list as JSON.
+        #[napi(ts_return_type = "Record<string, any> | null")]
+        pub fn get_default_configuration(&self, config_list_uuid: String) -> Result<serde_json::Value> {
+            match self.inner.get_default_configuration(&config_list_uuid) {
+                Some(config) => serde_json::to_value(crate::types::PlistValue::Object(config.to_plist()))
+                    .map_err(|e| Error::from_reason(e.to_string())),
+                None => Ok(serde_json::Value::Null),
+            }
+        }
+
         /// Set a build setting on all configurations for a target.
         #[napi]
         pub fn set_build_setting(&mut self, target_uuid: String, key: String, value: String) -> bool {
@@ -444,8 +549,22 @@ mod napi_bindings {
             self.inner.remove_build_setting(&target_uuid, &key)
         }
 
+        /// Get a target configuration's build settings merged over the project's, with
+        /// `$(VAR)` references resolved. Returns null if the target or configuration is missing.
+        #[napi(ts_return_type = "Record<string, string> | null")]
+        pub fn get_resolved_build_settings(
+            &self,
+            target_uuid: String,
+            config_name: String,
+        ) -> Result<serde_json::Value> {
+            match self.inner.resolved_build_settings(&target_uuid, &config_name) {
+                Some(settings) => serde_json::to_value(settings).map_err(|e| Error::from_reason(e.to_string())),
+                None => Ok(serde_json::Value::Null),
+            }
+        }
+
         /// Find orphaned references (UUIDs referenced but not present in objects).
-        /// Returns array of { referrerUuid, referrerIsa, property, orphanUuid }.
+        /// Returns array of { referrerUuid, referrerIsa, property, orphanUuid, inArray, suggestedAction }.
         #[napi(js_name = "findOrphanedReferences")]
         pub fn find_orphaned_references(&self) -> Vec<serde_json::Value> {
             self.inner
@@ -457,6 +576,8 @@ mod napi_bindings {
                         "referrerIsa": o.referrer_isa,
                         "property": o.property,
                         "orphanUuid": o.orphan_uuid,
+                        "inArray": o.in_array,
+                        "suggestedAction": o.suggested_action().as_str(),
                     })
                 })
                 .collect()
@@ -519,6 +640,15 @@ mod napi_bindings {
             self.inner.ensure_build_phase(&target_uuid, &phase_isa)
         }
 
+        /// Find a target's build phase of the given ISA without creating one.
+        /// Returns the UUID of the build phase.
+        #[napi]
+        pub fn get_build_phase(&self, target_uuid: String, phase_isa: String) -> Option<String> {
+            self.inner
+                .find_build_phase(&target_uuid, &phase_isa)
+                .map(|phase| phase.uuid.clone())
+        }
+
         /// Add a framework to a target.
         /// Returns the UUID of the PBXBuildFile.
         #[napi]
@@ -555,10 +685,22 @@ mod napi_bindings {
 
         /// Embed an extension target into a host app target.
         /// Creates PBXCopyFilesBuildPhase with correct dstSubfolderSpec.
-        /// Returns the UUID of the copy files build phase.
-        #[napi]
-        pub fn embed_extension(&mut self, host_target_uuid: String, extension_target_uuid: String) -> Option<String> {
-            self.inner.embed_extension(&host_target_uuid, &extension_target_uuid)
+        /// Returns { phaseUuid, buildFileUuid, dependencyUuid }.
+        #[napi(ts_return_type = "{ phaseUuid: string; buildFileUuid: string; dependencyUuid: string } | null")]
+        pub fn embed_extension(
+            &mut self,
+            host_target_uuid: String,
+            extension_target_uuid: String,
+        ) -> Option<serde_json::Value> {
+            self.inner
+                .embed_extension(&host_target_uuid, &extension_target_uuid)
+                .map(|e| {
+                    serde_json::json!({
+                        "phaseUuid": e.phase_uuid,
+                        "buildFileUuid": e.build_file_uuid,
+                        "dependencyUuid": e.dependency_uuid,
+                    })
+                })
         }
 
         /// Add a PBXFileSystemSynchronizedRootGroup to a target (Xcode 16+).
@@ -576,6 +718,24 @@ mod napi_bindings {
 
         // ── Generic property access ──────────────────────────────
 
+        /// Check whether an object with the given UUID exists.
+        #[napi]
+        pub fn has_object(&self, uuid: String) -> bool {
+            self.inner.contains(&uuid)
+        }
+
+        /// Number of objects in the project.
+        #[napi]
+        pub fn object_count(&self) -> u32 {
+            self.inner.object_count() as u32
+        }
+
+        /// Whether the project has no objects at all.
+        #[napi]
+        pub fn is_empty(&self) -> bool {
+            self.inner.is_empty()
+        }
+
         /// Get a string property from any object.
         #[napi]
         pub fn get_object_property(&self, uuid: String, key: String) -> Option<String> {
@@ -588,6 +748,20 @@ mod napi_bindings {
             self.inner.set_object_property(&uuid, &key, &value)
         }
 
+        /// Set a property of any JSON-compatible type on any object.
+        #[napi]
+        pub fn set_object_value(&mut self, uuid: String, key: String, value: serde_json::Value) -> Result<bool> {
+            let value: crate::types::PlistValue<'static> =
+                serde_json::from_value(value).map_err(|e| Error::from_reason(e.to_string()))?;
+            Ok(self.inner.set_object_value(&uuid, &key, value))
+        }
+
+        /// Count objects per ISA type, most common first.
+        #[napi(ts_return_type = "Record<string, number>")]
+        pub fn isa_histogram(&self) -> Result<serde_json::Value> {
+            serde_json::to_value(self.inner.isa_histogram()).map_err(|e| Error::from_reason(e.to_string()))
+        }
+
         /// Find all object UUIDs matching a given ISA type.
         #[napi]
         pub fn find_objects_by_isa(&self, isa: String) -> Vec<String> {
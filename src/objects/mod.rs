@@ -6,8 +6,8 @@ use crate::types::plist::{PlistMap, PlistObject, PlistValue};
 
 /// A trait providing shared behavior for all PBX object types.
 pub trait PbxObjectExt {
-    /// The ISA type of this object.
-    fn isa(&self) -> Isa;
+    /// The ISA type of this object, or `None` if the ISA isn't one [`Isa`] knows.
+    fn isa(&self) -> Option<Isa>;
 
     /// The UUID of this object.
     fn uuid(&self) -> &str;
@@ -21,6 +21,9 @@ pub trait PbxObjectExt {
     /// Remove all references to the given UUID from this object's properties.
     fn remove_reference(&mut self, uuid: &str);
 
+    /// Point every reference to `old_uuid` at `new_uuid` instead. Returns how many were rewritten.
+    fn replace_reference(&mut self, old_uuid: &str, new_uuid: &str) -> usize;
+
     /// Get all UUID references contained in this object (for inflation).
     fn get_reference_uuids(&self) -> Vec<String>;
 }
@@ -91,13 +94,36 @@ impl PbxObject {
         self.props.insert(Cow::Owned(key.to_string()), value);
     }
 
+    /// Set a property, inserting a new key in alphabetical position (after `isa`),
+    /// the order Xcode writes keys in.
+    pub fn set_sorted(&mut self, key: &str, value: PlistValue<'static>) {
+        if let Some(existing) = self.props.get_mut(key) {
+            *existing = value;
+            return;
+        }
+        let index = self
+            .props
+            .keys()
+            .position(|k| k != "isa" && k.as_ref() > key)
+            .unwrap_or(self.props.len());
+        self.props.shift_insert(index, Cow::Owned(key.to_string()), value);
+    }
+
     /// Remove a property.
     pub fn remove(&mut self, key: &str) -> Option<PlistValue<'static>> {
         self.props.shift_remove(key)
     }
 
     /// Get properties that are known to contain UUID references, based on ISA type.
-    pub fn reference_keys(&self) -> Vec<&str> {
+    ///
+    /// These are the built-in keys only. An object can't see keys registered with
+    /// [`XcodeProject::register_reference_keys`](crate::project::XcodeProject::register_reference_keys),
+    /// so [`reference`](Self::reference), [`references`](Self::references),
+    /// [`collect_references_by_key`](Self::collect_references_by_key) and the
+    /// [`PbxObjectExt`] reference methods ignore them. Pass
+    /// [`XcodeProject::reference_keys_for`](crate::project::XcodeProject::reference_keys_for)
+    /// to the `*_in` variants to include them.
+    pub fn reference_keys(&self) -> Vec<&'static str> {
         match self.isa.as_str() {
             "PBXProject" => vec![
                 "buildConfigurationList",
@@ -131,35 +157,162 @@ impl PbxObject {
         }
     }
 
+    /// Get a single-UUID reference property, e.g. a target's `buildConfigurationList`.
+    ///
+    /// Returns `None` unless `key` is one of [`reference_keys`](Self::reference_keys) and its
+    /// value is a non-empty string. IDs aren't required to be hex: generated projects use
+    /// ones like Expo's `XX…XX` or swift-protobuf's `__RootObject_`.
+    pub fn reference(&self, key: &str) -> Option<&str> {
+        if !self.reference_keys().contains(&key) {
+            return None;
+        }
+        self.get_str(key).filter(|s| !s.is_empty())
+    }
+
+    /// Get the UUIDs in an array reference property, e.g. a group's `children`.
+    ///
+    /// Empty unless `key` is one of [`reference_keys`](Self::reference_keys); empty and
+    /// non-string items are skipped.
+    pub fn references(&self, key: &str) -> Vec<&str> {
+        if !self.reference_keys().contains(&key) {
+            return Vec::new();
+        }
+        self.get_array(key)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Compare ISA and properties, ignoring the objects' own UUIDs.
+    ///
+    /// Two build files pointing at the same `fileRef` with identical settings compare equal.
+    /// Key order doesn't matter, in nested dictionaries like `settings` either; array order does.
+    pub fn structurally_equals(&self, other: &PbxObject) -> bool {
+        self.isa == other.isa
+            && self.props.len() == other.props.len()
+            && self.props.iter().all(|(key, value)| {
+                other
+                    .props
+                    .get(key)
+                    .is_some_and(|other_value| value.deep_eq_unordered(other_value))
+            })
+    }
+
+    /// Like [`structurally_equals`](Self::structurally_equals), but also ignores which UUIDs the
+    /// [`reference_keys`](Self::reference_keys) point at. Only their presence and, for arrays,
+    /// their length must match.
+    pub fn structurally_equals_ignoring_references(&self, other: &PbxObject) -> bool {
+        if self.isa != other.isa || self.props.len() != other.props.len() {
+            return false;
+        }
+        let reference_keys = self.reference_keys();
+        self.props.iter().all(|(key, value)| {
+            let Some(other_value) = other.props.get(key) else {
+                return false;
+            };
+            if !reference_keys.contains(&key.as_ref()) {
+                return value.deep_eq_unordered(other_value);
+            }
+            match (value, other_value) {
+                (PlistValue::String(_), PlistValue::String(_)) => true,
+                (PlistValue::Array(a), PlistValue::Array(b)) => a.len() == b.len(),
+                _ => value.deep_eq_unordered(other_value),
+            }
+        })
+    }
+
     /// Collect all UUID strings referenced by this object.
     pub fn collect_references(&self) -> HashSet<String> {
-        let mut refs = HashSet::new();
-        for key in self.reference_keys() {
-            if let Some(value) = self.props.get(key) {
+        self.collect_references_by_key()
+            .into_iter()
+            .map(|(_, uuid)| uuid)
+            .collect()
+    }
+
+    /// Collect every UUID referenced by this object, paired with the property it
+    /// came from. Properties are visited in [`reference_keys`](Self::reference_keys) order,
+    /// not the order they appear in the object. A UUID listed under two keys appears twice.
+    pub fn collect_references_by_key(&self) -> Vec<(&str, String)> {
+        self.collect_references_in(&self.reference_keys())
+    }
+
+    /// Like [`collect_references_by_key`](Self::collect_references_by_key), but reading
+    /// the given properties instead of the built-in [`reference_keys`](Self::reference_keys).
+    pub fn collect_references_in<'k>(&self, keys: &[&'k str]) -> Vec<(&'k str, String)> {
+        let mut refs = Vec::new();
+        for &key in keys {
+            match self.props.get(key) {
+                Some(PlistValue::String(s)) if !s.is_empty() => refs.push((key, s.to_string())),
+                Some(PlistValue::Array(items)) => {
+                    for s in items.iter().filter_map(|item| item.as_str()) {
+                        if !s.is_empty() {
+                            refs.push((key, s.to_string()));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        refs
+    }
+
+    /// Whether any of the given properties references `uuid`.
+    pub fn is_referencing_in(&self, keys: &[&str], uuid: &str) -> bool {
+        keys.iter().any(|&key| match self.props.get(key) {
+            Some(PlistValue::String(s)) => s.as_ref() == uuid,
+            Some(PlistValue::Array(items)) => items.iter().any(|item| item.as_str() == Some(uuid)),
+            _ => false,
+        })
+    }
+
+    /// Remove `uuid` from the given properties: array entries are dropped and
+    /// scalar references are blanked.
+    pub fn remove_reference_in(&mut self, keys: &[&str], uuid: &str) {
+        for &key in keys {
+            if let Some(value) = self.props.get_mut(key) {
                 match value {
-                    PlistValue::String(s) if looks_like_uuid(s) => {
-                        refs.insert(s.to_string());
+                    PlistValue::String(s) if s.as_ref() == uuid => {
+                        *value = PlistValue::String(Cow::Owned(String::new()));
                     }
                     PlistValue::Array(items) => {
-                        for item in items {
-                            if let Some(s) = item.as_str() {
-                                if looks_like_uuid(s) {
-                                    refs.insert(s.to_string());
-                                }
-                            }
-                        }
+                        items.retain(|item| item.as_str() != Some(uuid));
                     }
                     _ => {}
                 }
             }
         }
-        refs
+    }
+
+    /// Rewrite references to `old_uuid` in the given properties. Returns the number rewritten.
+    pub fn replace_reference_in(&mut self, keys: &[&str], old_uuid: &str, new_uuid: &str) -> usize {
+        let mut replaced = 0;
+        for &key in keys {
+            let Some(value) = self.props.get_mut(key) else {
+                continue;
+            };
+            let slots: Vec<&mut PlistValue<'static>> = match value {
+                PlistValue::Array(items) => items.iter_mut().collect(),
+                other => vec![other],
+            };
+            for slot in slots {
+                if slot.as_str() == Some(old_uuid) {
+                    *slot = PlistValue::String(Cow::Owned(new_uuid.to_string()));
+                    replaced += 1;
+                }
+            }
+        }
+        replaced
     }
 }
 
 impl PbxObjectExt for PbxObject {
-    fn isa(&self) -> Isa {
-        self.isa.parse().unwrap_or(Isa::PBXBuildFile)
+    fn isa(&self) -> Option<Isa> {
+        self.isa.parse().ok()
     }
 
     fn uuid(&self) -> &str {
@@ -174,37 +327,17 @@ impl PbxObjectExt for PbxObject {
     }
 
     fn is_referencing(&self, uuid: &str) -> bool {
-        for key in self.reference_keys() {
-            if let Some(value) = self.props.get(key) {
-                match value {
-                    PlistValue::String(s) if s.as_ref() == uuid => return true,
-                    PlistValue::Array(items) => {
-                        if items.iter().any(|item| item.as_str() == Some(uuid)) {
-                            return true;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        false
+        self.is_referencing_in(&self.reference_keys(), uuid)
     }
 
     fn remove_reference(&mut self, uuid: &str) {
-        let keys: Vec<String> = self.reference_keys().iter().map(|k| k.to_string()).collect();
-        for key in keys {
-            if let Some(value) = self.props.get_mut(key.as_str()) {
-                match value {
-                    PlistValue::String(s) if s.as_ref() == uuid => {
-                        *value = PlistValue::String(Cow::Owned(String::new()));
-                    }
-                    PlistValue::Array(items) => {
-                        items.retain(|item| item.as_str() != Some(uuid));
-                    }
-                    _ => {}
-                }
-            }
-        }
+        let keys = self.reference_keys();
+        self.remove_reference_in(&keys, uuid);
+    }
+
+    fn replace_reference(&mut self, old_uuid: &str, new_uuid: &str) -> usize {
+        let keys = self.reference_keys();
+        self.replace_reference_in(&keys, old_uuid, new_uuid)
     }
 
     fn get_reference_uuids(&self) -> Vec<String> {
@@ -212,11 +345,6 @@ impl PbxObjectExt for PbxObject {
     }
 }
 
-/// Heuristic: a 24-char hex string is likely a UUID.
-fn looks_like_uuid(s: &str) -> bool {
-    s.len() == 24 && s.chars().all(|c| c.is_ascii_hexdigit())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,4 +386,174 @@ mod tests {
         assert_eq!(children.len(), 1);
         assert_eq!(children[0].as_str(), Some("BBBB00000000000000000002"));
     }
+
+    #[test]
+    fn test_reference_getters() {
+        let props: PlistObject<'static> = vec![
+            (
+                Cow::Owned("isa".to_string()),
+                PlistValue::String("PBXNativeTarget".into()),
+            ),
+            (
+                Cow::Owned("buildConfigurationList".to_string()),
+                PlistValue::String("13B07F931A680F5B00A75B9A".into()),
+            ),
+            (
+                Cow::Owned("buildPhases".to_string()),
+                PlistValue::Array(vec![
+                    PlistValue::String("13B07F871A680F5B00A75B9A".into()),
+                    PlistValue::String("".into()),
+                    PlistValue::String("13B07E8C1A680F5B00A75B9A".into()),
+                ]),
+            ),
+            (
+                Cow::Owned("name".to_string()),
+                PlistValue::String("AABB00112233445566778899".into()),
+            ),
+            (
+                Cow::Owned("productReference".to_string()),
+                PlistValue::String("".into()),
+            ),
+        ];
+        let obj = PbxObject::from_plist("13B07F861A680F5B00A75B9A".to_string(), &props);
+
+        assert_eq!(
+            obj.reference("buildConfigurationList"),
+            Some("13B07F931A680F5B00A75B9A")
+        );
+        assert_eq!(obj.reference("productReference"), None);
+        assert_eq!(obj.reference("missing"), None);
+        // UUID-shaped values outside the reference keys aren't references.
+        assert_eq!(obj.reference("name"), None);
+        // Array properties aren't single references.
+        assert_eq!(obj.reference("buildPhases"), None);
+
+        assert_eq!(
+            obj.references("buildPhases"),
+            vec!["13B07F871A680F5B00A75B9A", "13B07E8C1A680F5B00A75B9A"]
+        );
+        assert!(obj.references("buildConfigurationList").is_empty());
+        assert!(obj.references("dependencies").is_empty());
+    }
+
+    #[test]
+    fn test_structurally_equals() {
+        let build_file = |uuid: &str, file_ref: &str, attributes: &str| {
+            let props: PlistObject<'static> = vec![
+                (Cow::Owned("isa".to_string()), PlistValue::String("PBXBuildFile".into())),
+                (
+                    Cow::Owned("fileRef".to_string()),
+                    PlistValue::String(Cow::Owned(file_ref.to_string())),
+                ),
+                (
+                    Cow::Owned("settings".to_string()),
+                    PlistValue::Object(vec![(
+                        Cow::Owned("ATTRIBUTES".to_string()),
+                        PlistValue::Array(vec![PlistValue::String(Cow::Owned(attributes.to_string()))]),
+                    )]),
+                ),
+            ];
+            PbxObject::from_plist(uuid.to_string(), &props)
+        };
+
+        let a = build_file("AAAA00000000000000000001", "FFFF00000000000000000001", "Weak");
+        let same = build_file("AAAA00000000000000000002", "FFFF00000000000000000001", "Weak");
+        let other_ref = build_file("AAAA00000000000000000003", "FFFF00000000000000000002", "Weak");
+        let other_settings = build_file("AAAA00000000000000000004", "FFFF00000000000000000001", "Required");
+
+        assert!(a.structurally_equals(&same));
+        assert!(!a.structurally_equals(&other_ref));
+        assert!(!a.structurally_equals(&other_settings));
+
+        assert!(a.structurally_equals_ignoring_references(&other_ref));
+        assert!(!a.structurally_equals_ignoring_references(&other_settings));
+
+        // Property order is irrelevant.
+        let mut reordered = same.clone();
+        let file_ref = reordered.remove("fileRef").unwrap();
+        reordered.set("fileRef", file_ref);
+        assert!(a.structurally_equals(&reordered));
+
+        // So is key order inside nested dictionaries.
+        let settings = |pairs: &[(&str, &str)]| {
+            PlistValue::Object(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (Cow::Owned(k.to_string()), PlistValue::String(Cow::Owned(v.to_string()))))
+                    .collect(),
+            )
+        };
+        let mut nested = a.clone();
+        nested.set(
+            "settings",
+            settings(&[("ATTRIBUTES", "Weak"), ("COMPILER_FLAGS", "-w")]),
+        );
+        let mut nested_reordered = same.clone();
+        nested_reordered.set(
+            "settings",
+            settings(&[("COMPILER_FLAGS", "-w"), ("ATTRIBUTES", "Weak")]),
+        );
+        assert!(nested.structurally_equals(&nested_reordered));
+        assert!(nested.structurally_equals_ignoring_references(&nested_reordered));
+
+        let mut retagged = same.clone();
+        retagged.isa = "PBXFileReference".to_string();
+        assert!(!a.structurally_equals(&retagged));
+        assert!(!a.structurally_equals_ignoring_references(&retagged));
+    }
+
+    #[test]
+    fn test_unknown_isa_is_not_a_build_file() {
+        let props: PlistObject<'static> = vec![(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String("PBXFutureThing".into()),
+        )];
+        let obj = PbxObject::from_plist("AABB00112233445566778899".to_string(), &props);
+        assert_eq!(obj.isa(), None);
+        assert_ne!(obj.isa(), Some(Isa::PBXBuildFile));
+
+        let props: PlistObject<'static> =
+            vec![(Cow::Owned("isa".to_string()), PlistValue::String("PBXBuildFile".into()))];
+        let obj = PbxObject::from_plist("AABB00112233445566778899".to_string(), &props);
+        assert_eq!(obj.isa(), Some(Isa::PBXBuildFile));
+    }
+
+    #[test]
+    fn test_collect_references_by_key() {
+        let props: PlistObject<'static> = vec![
+            (
+                Cow::Owned("isa".to_string()),
+                PlistValue::String("PBXNativeTarget".into()),
+            ),
+            (
+                Cow::Owned("buildConfigurationList".to_string()),
+                PlistValue::String("AAAA00000000000000000001".into()),
+            ),
+            (
+                Cow::Owned("buildPhases".to_string()),
+                PlistValue::Array(vec![
+                    PlistValue::String("AAAA00000000000000000002".into()),
+                    PlistValue::String("AAAA00000000000000000003".into()),
+                ]),
+            ),
+            (
+                Cow::Owned("dependencies".to_string()),
+                PlistValue::Array(vec![PlistValue::String("AAAA00000000000000000004".into())]),
+            ),
+            (Cow::Owned("name".to_string()), PlistValue::String("App".into())),
+        ];
+        let obj = PbxObject::from_plist("BBBB00000000000000000000".to_string(), &props);
+
+        let refs = obj.collect_references_by_key();
+        assert_eq!(
+            refs,
+            vec![
+                ("buildConfigurationList", "AAAA00000000000000000001".to_string()),
+                ("dependencies", "AAAA00000000000000000004".to_string()),
+                ("buildPhases", "AAAA00000000000000000002".to_string()),
+                ("buildPhases", "AAAA00000000000000000003".to_string()),
+            ]
+        );
+        assert_eq!(obj.collect_references().len(), 4);
+    }
 }
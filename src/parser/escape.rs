@@ -49,6 +49,20 @@ fn nextstep_to_unicode(code: u32) -> u32 {
     code
 }
 
+/// Reverse of `nextstep_to_unicode`: the NeXTSTEP byte for a Unicode character, if it has one.
+///
+/// U+FFFD is excluded since two NeXTSTEP bytes map to it.
+pub fn unicode_to_nextstep(ch: char) -> Option<u8> {
+    let code = ch as u32;
+    if code < 0x80 || code == 0xfffd {
+        return None;
+    }
+    NEXT_STEP_MAPPINGS
+        .iter()
+        .find(|&&(_, unicode)| unicode == code)
+        .map(|&(byte, _)| byte)
+}
+
 /// Process escape sequences in a quoted string (with quotes already stripped).
 ///
 /// Replicates the `stripQuotes` function from identifiers.ts.
@@ -112,9 +126,11 @@ pub fn unescape_string(input: &str) -> String {
                 }
                 // Unicode escape: \Uxxxx
                 b'U' if i + 5 < len => {
-                    let hex = &input[i + 2..i + 6];
-                    if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                        let code = u32::from_str_radix(hex, 16).unwrap();
+                    let code = input
+                        .get(i + 2..i + 6)
+                        .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                        .and_then(|hex| u32::from_str_radix(hex, 16).ok());
+                    if let Some(code) = code {
                         if let Some(ch) = char::from_u32(code) {
                             result.push(ch);
                         }
@@ -140,18 +156,28 @@ pub fn unescape_string(input: &str) -> String {
                     i = j;
                 }
                 // Unknown escape — preserve both characters
-                _ => {
+                _ if next.is_ascii() => {
                     result.push('\\');
                     result.push(next as char);
                     i += 2;
                 }
+                // Backslash before a multi-byte character — keep the backslash
+                // and let the character be decoded on the next iteration.
+                _ => {
+                    result.push('\\');
+                    i += 1;
+                }
             }
         } else {
             // Safety: we're iterating byte by byte but need to handle UTF-8.
             // For non-ASCII bytes, decode the full character.
-            let ch = input[i..].chars().next().unwrap();
-            result.push(ch);
-            i += ch.len_utf8();
+            match input.get(i..).and_then(|rest| rest.chars().next()) {
+                Some(ch) => {
+                    result.push(ch);
+                    i += ch.len_utf8();
+                }
+                None => i += 1,
+            }
         }
     }
 
@@ -199,6 +225,15 @@ mod tests {
         assert_eq!(unescape_string(r"\341"), "\u{00c6}");
     }
 
+    #[test]
+    fn test_unicode_to_nextstep() {
+        assert_eq!(unicode_to_nextstep('\u{00a9}'), Some(0xa0));
+        assert_eq!(unicode_to_nextstep('\u{00c6}'), Some(0xe1));
+        assert_eq!(unicode_to_nextstep('A'), None);
+        assert_eq!(unicode_to_nextstep('\u{fffd}'), None);
+        assert_eq!(unicode_to_nextstep('\u{4e2d}'), None);
+    }
+
     #[test]
     fn test_no_escape() {
         assert_eq!(unescape_string("hello world"), "hello world");
@@ -210,4 +245,11 @@ mod tests {
         assert_eq!(unescape_string(r"hello\nworld"), "hello\nworld");
         assert_eq!(unescape_string(r"path/to/\tfile"), "path/to/\tfile");
     }
+
+    #[test]
+    fn test_escape_before_multibyte_char() {
+        assert_eq!(unescape_string("\\é"), "\\é");
+        assert_eq!(unescape_string("\\Ué00"), "\\Ué00");
+        assert_eq!(unescape_string("\\U00é"), "\\U00é");
+    }
 }
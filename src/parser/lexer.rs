@@ -50,16 +50,24 @@ pub enum Token {
 pub struct Lexer<'a> {
     input: &'a [u8],
     pos: usize,
+    max_data_bytes: usize,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Self {
         Lexer {
             input: input.as_bytes(),
-            pos: 0,
+            pos: super::parser::bom_len(input),
+            max_data_bytes: usize::MAX,
         }
     }
 
+    /// Set the maximum decoded size of a `<...>` data literal (default: unlimited).
+    pub fn with_max_data_bytes(mut self, max_data_bytes: usize) -> Self {
+        self.max_data_bytes = max_data_bytes;
+        self
+    }
+
     /// Skip whitespace and comments in bulk using fast byte scanning.
     #[inline]
     fn skip_trivia(&mut self) {
@@ -168,25 +176,7 @@ impl<'a> Lexer<'a> {
         let hex_region = &bytes[start..self.pos];
         self.pos += 1; // skip >
 
-        let mut hex = String::with_capacity(hex_region.len());
-        for &b in hex_region {
-            if b.is_ascii_hexdigit() {
-                hex.push(b as char);
-            } else if b.is_ascii_whitespace() {
-                // skip
-            } else {
-                return Err(format!("Invalid character in data literal: {}", b as char));
-            }
-        }
-
-        let result_bytes = (0..hex.len())
-            .step_by(2)
-            .map(|i| {
-                let end = (i + 2).min(hex.len());
-                u8::from_str_radix(&hex[i..end], 16).map_err(|e| format!("Invalid hex: {}", e))
-            })
-            .collect::<Result<Vec<u8>, _>>()?;
-
+        let result_bytes = super::parser::decode_data_literal(hex_region, self.max_data_bytes, start - 1)?;
         Ok(Token::DataLiteral(result_bytes))
     }
 
@@ -261,12 +251,58 @@ impl<'a> Lexer<'a> {
         }
         Ok(tokens)
     }
+
+    /// Tokenize the entire input, recording each token's `[start, end)` byte offsets.
+    /// Quoted strings and data literals span their delimiters.
+    pub fn tokenize_spanned(&mut self) -> Result<Vec<(Token, usize, usize)>, String> {
+        let mut tokens = Vec::with_capacity(self.input.len() / 8);
+        loop {
+            self.skip_trivia();
+            let start = self.pos;
+            match self.next_token()? {
+                Some(tok) => tokens.push((tok, start, self.pos)),
+                None => return Ok(tokens),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_tokenize_spanned() {
+        let input = "{ name = \"My App\"; /* c */ data = <0A0B>; list = (a, b); }";
+        let spans = Lexer::new(input).tokenize_spanned().unwrap();
+        let texts: Vec<&str> = spans.iter().map(|&(_, start, end)| &input[start..end]).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "{",
+                "name",
+                "=",
+                "\"My App\"",
+                ";",
+                "data",
+                "=",
+                "<0A0B>",
+                ";",
+                "list",
+                "=",
+                "(",
+                "a",
+                ",",
+                "b",
+                ")",
+                ";",
+                "}"
+            ]
+        );
+        assert_eq!(spans[3].0, Token::QuotedString("My App".to_string()));
+        assert_eq!(spans[7].0, Token::DataLiteral(vec![0x0a, 0x0b]));
+    }
+
     #[test]
     fn test_structural_tokens() {
         let mut lexer = Lexer::new("{ } ( ) = ; ,");
@@ -352,4 +388,13 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_data_literal_size_limit() {
+        let input = format!("<{}>", "ff".repeat(33));
+        let err = Lexer::new(&input).with_max_data_bytes(32).tokenize_all().unwrap_err();
+        assert!(err.contains("limit of 32 bytes"), "{}", err);
+        let tokens = Lexer::new(&input).with_max_data_bytes(33).tokenize_all().unwrap();
+        assert_eq!(tokens, vec![Token::DataLiteral(vec![0xff; 33])]);
+    }
 }
@@ -2,4 +2,7 @@ pub mod escape;
 pub mod lexer;
 pub mod parser;
 
-pub use parser::parse;
+pub use parser::{
+    parse, parse_bounded, parse_untrusted, parse_with_quote_styles, parse_with_stats, ParseLimits, ParseStats,
+    QuoteSite, QuoteStyle, QuoteStyles,
+};
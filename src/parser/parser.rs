@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use super::escape::unescape_string;
 use crate::types::PlistValue;
@@ -6,6 +7,10 @@ use crate::types::PlistValue;
 /// JS MAX_SAFE_INTEGER (2^53 - 1)
 const MAX_SAFE_INTEGER: i64 = 9007199254740991;
 
+/// Default limit on `{}` / `()` nesting. Real projects stay well under 10;
+/// the limit only exists so adversarial input can't overflow the stack.
+pub const DEFAULT_MAX_DEPTH: usize = 512;
+
 /// Lookup table for unquoted string literal characters: `[a-zA-Z0-9_$/:.-]`.
 static IS_LITERAL_CHAR: [bool; 256] = {
     let mut t = [false; 256];
@@ -33,6 +38,82 @@ static IS_LITERAL_CHAR: [bool; 256] = {
     t
 };
 
+/// Statistics collected while parsing, for diagnosing pathological projects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Number of tokens consumed (punctuation, strings, numbers, data literals).
+    pub token_count: usize,
+    /// Number of entries in the root `objects` dictionary.
+    pub object_count: usize,
+    /// Deepest nesting of `{}` / `()` containers.
+    pub max_depth: usize,
+    /// Number of input bytes consumed by the parser.
+    pub bytes_consumed: usize,
+}
+
+/// Resource limits for [`parse_bounded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Largest accepted input, in bytes.
+    pub max_bytes: usize,
+    /// Most entries allowed in the root `objects` dictionary.
+    pub max_objects: usize,
+    /// Deepest allowed `{}` / `()` nesting.
+    pub max_depth: usize,
+    /// Largest accepted `<...>` data literal, in decoded bytes.
+    pub max_data_bytes: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        ParseLimits {
+            max_bytes: usize::MAX,
+            max_objects: usize::MAX,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_data_bytes: usize::MAX,
+        }
+    }
+}
+
+/// How a string was quoted in the source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// `'single'`
+    Single,
+    /// `"double"`
+    Double,
+}
+
+/// Where a quoted string occurred: the dictionary keys and array indices leading to
+/// it, and whether it was the last dictionary key itself rather than its value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuoteSite {
+    pub path: Vec<String>,
+    pub is_key: bool,
+}
+
+impl QuoteSite {
+    /// The key at the end of `path`, e.g. `["objects", "<uuid>", "name"]`.
+    pub fn key(path: &[&str]) -> Self {
+        QuoteSite {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            is_key: true,
+        }
+    }
+
+    /// The value at `path`; array items are addressed by index, e.g. `["list", "0"]`.
+    pub fn value(path: &[&str]) -> Self {
+        QuoteSite {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            is_key: false,
+        }
+    }
+}
+
+/// Quote style of each quoted string in a parsed file, by where it occurred, so the
+/// same text can be quoted differently in different places. See [`parse_with_quote_styles`].
+pub type QuoteStyles = HashMap<QuoteSite, QuoteStyle>;
+
 /// Single-pass recursive descent parser for .pbxproj (Old-Style Plist) files.
 ///
 /// Zero-copy: string values borrow directly from the input where possible.
@@ -40,16 +121,78 @@ static IS_LITERAL_CHAR: [bool; 256] = {
 pub struct Parser<'a> {
     input: &'a [u8],
     pos: usize,
+    depth: usize,
+    max_depth: usize,
+    max_objects: usize,
+    max_data_bytes: usize,
+    /// Set while parsing the value of the root `objects` key.
+    in_objects: bool,
+    stats: ParseStats,
+    quote_styles: Option<QuoteStyles>,
+    /// Keys and array indices leading to the current value, kept only while recording quote styles.
+    quote_path: Vec<String>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(input: &'a str) -> Self {
         Parser {
             input: input.as_bytes(),
-            pos: 0,
+            pos: bom_len(input),
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_objects: usize::MAX,
+            max_data_bytes: usize::MAX,
+            in_objects: false,
+            stats: ParseStats::default(),
+            quote_styles: None,
+            quote_path: Vec::new(),
         }
     }
 
+    /// Set the maximum container nesting depth (default: [`DEFAULT_MAX_DEPTH`]).
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set the maximum number of entries in the root `objects` dictionary (default: unlimited).
+    pub fn with_max_objects(mut self, max_objects: usize) -> Self {
+        self.max_objects = max_objects;
+        self
+    }
+
+    /// Set the maximum decoded size of a `<...>` data literal (default: unlimited).
+    pub fn with_max_data_bytes(mut self, max_data_bytes: usize) -> Self {
+        self.max_data_bytes = max_data_bytes;
+        self
+    }
+
+    /// Record the quote style of every quoted string (off by default).
+    pub fn with_quote_styles(mut self) -> Self {
+        self.quote_styles = Some(QuoteStyles::new());
+        self
+    }
+
+    /// Statistics collected so far.
+    pub fn stats(&self) -> &ParseStats {
+        &self.stats
+    }
+
+    #[inline]
+    fn enter_container(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(format!(
+                "Maximum nesting depth of {} exceeded at offset {}",
+                self.max_depth, self.pos
+            ));
+        }
+        if self.depth > self.stats.max_depth {
+            self.stats.max_depth = self.depth;
+        }
+        Ok(())
+    }
+
     #[inline]
     fn skip_trivia(&mut self) {
         let bytes = self.input;
@@ -102,6 +245,7 @@ impl<'a> Parser<'a> {
         self.skip_trivia();
         if self.pos < self.input.len() && self.input[self.pos] == expected {
             self.pos += 1;
+            self.stats.token_count += 1;
             Ok(())
         } else {
             let found = self.input.get(self.pos).map(|&b| b as char);
@@ -166,6 +310,22 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Record that the string at the current path was quoted with `quote`.
+    fn record_quote(&mut self, quote: u8, is_key: bool) {
+        if let Some(styles) = self.quote_styles.as_mut() {
+            let style = if quote == b'\'' {
+                QuoteStyle::Single
+            } else {
+                QuoteStyle::Double
+            };
+            let site = QuoteSite {
+                path: self.quote_path.clone(),
+                is_key,
+            };
+            styles.insert(site, style);
+        }
+    }
+
     /// Read a data literal `<hex bytes>`.
     fn read_data_literal(&mut self) -> Result<PlistValue<'a>, String> {
         self.pos += 1; // skip <
@@ -184,25 +344,7 @@ impl<'a> Parser<'a> {
         let hex_region = &bytes[start..self.pos];
         self.pos += 1; // skip >
 
-        let mut hex = String::with_capacity(hex_region.len());
-        for &b in hex_region {
-            if b.is_ascii_hexdigit() {
-                hex.push(b as char);
-            } else if b.is_ascii_whitespace() {
-                // skip
-            } else {
-                return Err(format!("Invalid character in data literal: {}", b as char));
-            }
-        }
-
-        let result_bytes = (0..hex.len())
-            .step_by(2)
-            .map(|i| {
-                let end = (i + 2).min(hex.len());
-                u8::from_str_radix(&hex[i..end], 16).map_err(|e| format!("Invalid hex: {}", e))
-            })
-            .collect::<Result<Vec<u8>, _>>()?;
-
+        let result_bytes = decode_data_literal(hex_region, self.max_data_bytes, start - 1)?;
         Ok(PlistValue::Data(result_bytes))
     }
 
@@ -210,6 +352,7 @@ impl<'a> Parser<'a> {
     #[inline]
     fn read_identifier(&mut self) -> Result<Cow<'a, str>, String> {
         self.skip_trivia();
+        self.stats.token_count += 1;
         match self.input.get(self.pos) {
             Some(b'"') | Some(b'\'') => self.read_quoted_string_cow(),
             Some(&b) if IS_LITERAL_CHAR[b as usize] => Ok(Cow::Borrowed(self.read_string_literal_ref())),
@@ -232,19 +375,43 @@ impl<'a> Parser<'a> {
 
     fn parse_object(&mut self) -> Result<PlistValue<'a>, String> {
         self.pos += 1; // skip {
+        self.stats.token_count += 1;
+        self.enter_container()?;
         let mut pairs = Vec::new();
 
         loop {
             match self.peek_byte() {
                 Some(b'}') => {
                     self.pos += 1;
+                    self.stats.token_count += 1;
+                    self.depth -= 1;
                     return Ok(PlistValue::Object(pairs));
                 }
                 None => return Err("Unterminated object".to_string()),
                 _ => {
+                    if self.in_objects && self.depth == 2 && pairs.len() >= self.max_objects {
+                        return Err(format!(
+                            "Maximum object count of {} exceeded at offset {}",
+                            self.max_objects, self.pos
+                        ));
+                    }
+                    let tracking = self.quote_styles.is_some();
+                    let key_quote = if tracking { self.peek_byte() } else { None };
                     let key = self.read_identifier()?;
+                    if tracking {
+                        self.quote_path.push(key.to_string());
+                        if let Some(quote @ (b'"' | b'\'')) = key_quote {
+                            self.record_quote(quote, true);
+                        }
+                    }
                     self.expect_byte(b'=')?;
+                    let is_objects = self.depth == 1 && key == "objects";
+                    self.in_objects |= is_objects;
                     let value = self.parse_value()?;
+                    self.in_objects &= !is_objects;
+                    if tracking {
+                        self.quote_path.pop();
+                    }
                     self.expect_byte(b';')?;
                     pairs.push((key, value));
                 }
@@ -254,19 +421,31 @@ impl<'a> Parser<'a> {
 
     fn parse_array(&mut self) -> Result<PlistValue<'a>, String> {
         self.pos += 1; // skip (
+        self.stats.token_count += 1;
+        self.enter_container()?;
         let mut items = Vec::new();
 
         loop {
             match self.peek_byte() {
                 Some(b')') => {
                     self.pos += 1;
+                    self.stats.token_count += 1;
+                    self.depth -= 1;
                     return Ok(PlistValue::Array(items));
                 }
                 None => return Err("Unterminated array".to_string()),
                 _ => {
+                    let tracking = self.quote_styles.is_some();
+                    if tracking {
+                        self.quote_path.push(items.len().to_string());
+                    }
                     items.push(self.parse_value()?);
+                    if tracking {
+                        self.quote_path.pop();
+                    }
                     if let Some(b',') = self.peek_byte() {
                         self.pos += 1;
+                        self.stats.token_count += 1;
                     }
                 }
             }
@@ -277,12 +456,18 @@ impl<'a> Parser<'a> {
         match self.peek_byte() {
             Some(b'{') => self.parse_object(),
             Some(b'(') => self.parse_array(),
-            Some(b'<') => self.read_data_literal(),
-            Some(b'"') | Some(b'\'') => {
+            Some(b'<') => {
+                self.stats.token_count += 1;
+                self.read_data_literal()
+            }
+            Some(quote @ (b'"' | b'\'')) => {
+                self.stats.token_count += 1;
                 let s = self.read_quoted_string_cow()?;
+                self.record_quote(quote, false);
                 Ok(PlistValue::String(s))
             }
             Some(b) if IS_LITERAL_CHAR[b as usize] => {
+                self.stats.token_count += 1;
                 let s = self.read_string_literal_ref();
                 Ok(parse_type(s))
             }
@@ -358,12 +543,119 @@ fn parse_type<'a>(literal: &'a str) -> PlistValue<'a> {
     PlistValue::String(Cow::Borrowed(literal))
 }
 
+/// Decode the hex digits between a data literal's `<` and `>`, skipping whitespace.
+///
+/// The digits are counted before anything is allocated, so a literal decoding to more
+/// than `max_bytes` is rejected without buffering it. An odd trailing digit decodes
+/// as a byte of its own.
+pub(crate) fn decode_data_literal(region: &[u8], max_bytes: usize, offset: usize) -> Result<Vec<u8>, String> {
+    let mut digits = 0usize;
+    for &b in region {
+        if b.is_ascii_hexdigit() {
+            digits += 1;
+        } else if !b.is_ascii_whitespace() {
+            return Err(format!("Invalid character in data literal: {}", b as char));
+        }
+    }
+    let len = digits.div_ceil(2);
+    if len > max_bytes {
+        return Err(format!(
+            "Data literal of {} bytes at offset {} exceeds the limit of {} bytes",
+            len, offset, max_bytes
+        ));
+    }
+
+    let mut data = Vec::with_capacity(len);
+    let mut pending: Option<u8> = None;
+    for &b in region.iter().filter(|b| b.is_ascii_hexdigit()) {
+        let nibble = (b as char).to_digit(16).unwrap_or(0) as u8;
+        match pending.take() {
+            Some(high) => data.push(high << 4 | nibble),
+            None => pending = Some(nibble),
+        }
+    }
+    data.extend(pending);
+    Ok(data)
+}
+
+/// Length of a leading UTF-8 byte order mark, if present.
+///
+/// Parsing starts after the BOM so reported offsets still index into the
+/// original input.
+#[inline]
+pub(crate) fn bom_len(input: &str) -> usize {
+    if input.starts_with('\u{FEFF}') {
+        '\u{FEFF}'.len_utf8()
+    } else {
+        0
+    }
+}
+
 /// Parse a .pbxproj string into a PlistValue.
+///
+/// Accepts any old-style plist: the root may be a dictionary (as in `.pbxproj`) or an
+/// array. Callers that require a dictionary root, such as
+/// [`XcodeProject`](crate::project::XcodeProject), check for it themselves.
 pub fn parse<'a>(text: &'a str) -> Result<PlistValue<'a>, String> {
     let mut parser = Parser::new(text);
     parser.parse_head()
 }
 
+/// Parse a .pbxproj string and report statistics gathered during the parse.
+pub fn parse_with_stats<'a>(text: &'a str) -> Result<(PlistValue<'a>, ParseStats), String> {
+    let mut parser = Parser::new(text);
+    let value = parser.parse_head()?;
+    let mut stats = parser.stats;
+    stats.object_count = value
+        .get("objects")
+        .and_then(|v| v.as_object())
+        .map_or(0, |objects| objects.len());
+    stats.bytes_consumed = parser.pos;
+    Ok((value, stats))
+}
+
+/// Parse a .pbxproj string and record how each quoted string was quoted, so
+/// [`build_with_quote_styles`](crate::writer::serializer::build_with_quote_styles)
+/// can reproduce single quotes and redundant double quotes.
+///
+/// Styles are keyed by content: if the same string is quoted differently in
+/// several places, the last occurrence wins.
+pub fn parse_with_quote_styles<'a>(text: &'a str) -> Result<(PlistValue<'a>, QuoteStyles), String> {
+    let mut parser = Parser::new(text).with_quote_styles();
+    let value = parser.parse_head()?;
+    Ok((value, parser.quote_styles.unwrap_or_default()))
+}
+
+/// Parse a .pbxproj string, failing as soon as any of `limits` is exceeded.
+///
+/// Meant for services accepting untrusted uploads: oversized input is rejected
+/// before parsing starts, and the object count and nesting depth are checked
+/// as the parse proceeds.
+pub fn parse_bounded<'a>(text: &'a str, limits: ParseLimits) -> Result<PlistValue<'a>, String> {
+    if text.len() > limits.max_bytes {
+        return Err(format!(
+            "Input of {} bytes exceeds the limit of {} bytes",
+            text.len(),
+            limits.max_bytes
+        ));
+    }
+    let mut parser = Parser::new(text)
+        .with_max_depth(limits.max_depth)
+        .with_max_objects(limits.max_objects)
+        .with_max_data_bytes(limits.max_data_bytes);
+    parser.parse_head()
+}
+
+/// Parse untrusted input under `limits`, converting any internal panic into an error.
+///
+/// Like [`parse_bounded`], but a parser bug can never take down the calling
+/// service: the parse runs inside [`std::panic::catch_unwind`] and a panic is
+/// reported as an `Err` instead of unwinding further.
+pub fn parse_untrusted<'a>(text: &'a str, limits: ParseLimits) -> Result<PlistValue<'a>, String> {
+    std::panic::catch_unwind(|| parse_bounded(text, limits))
+        .unwrap_or_else(|_| Err("Internal parser error on untrusted input".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,4 +764,228 @@ mod tests {
         assert_eq!(parse_type("5.0"), PlistValue::String(Cow::Borrowed("5.0")));
         assert_eq!(parse_type("3.14"), PlistValue::Float(3.14));
     }
+
+    #[test]
+    fn test_parse_with_stats() {
+        let input = "{ a = 1; b = ( x, y ); c = { d = \"e\"; }; }";
+        let (value, stats) = parse_with_stats(input).unwrap();
+        assert_eq!(value, parse(input).unwrap());
+        // { a = 1 ; b = ( x , y ) ; c = { d = "e" ; } ; }
+        assert_eq!(stats.token_count, 23);
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(stats.object_count, 0);
+        assert_eq!(stats.bytes_consumed, input.len());
+    }
+
+    #[test]
+    fn test_deep_nesting_errors_cleanly() {
+        let input = format!("{}{{}}{}", "{ a = ".repeat(2000), " ; }".repeat(2000));
+        let err = parse(&input).unwrap_err();
+        assert!(err.contains("Maximum nesting depth"), "unexpected error: {}", err);
+
+        let arrays = format!("{}{}", "(".repeat(2000), ")".repeat(2000));
+        assert!(parse(&arrays).is_err());
+    }
+
+    #[test]
+    fn test_custom_max_depth() {
+        let input = "{ a = { b = { c = 1; }; }; }";
+        assert!(Parser::new(input).with_max_depth(3).parse_head().is_ok());
+        assert!(Parser::new(input).with_max_depth(2).parse_head().is_err());
+    }
+
+    #[test]
+    fn test_leading_bom_is_skipped() {
+        let result = parse("\u{FEFF}{ key = value; }").unwrap();
+        assert_eq!(result.get("key").and_then(|v| v.as_str()), Some("value"));
+        assert!(parse("\u{FEFF}").is_err());
+    }
+
+    #[test]
+    fn test_parse_array_root() {
+        let result = parse("( a, b, c )").unwrap();
+        let items: Vec<&str> = result.as_array().unwrap().iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(items, vec!["a", "b", "c"]);
+
+        let nested = parse("({ isa = PBXGroup; }, ())").unwrap();
+        let items = nested.as_array().unwrap();
+        assert_eq!(items[0].get("isa").and_then(|v| v.as_str()), Some("PBXGroup"));
+        assert_eq!(items[1].as_array().map(|a| a.len()), Some(0));
+    }
+
+    #[test]
+    fn test_parse_with_quote_styles() {
+        let (value, styles) =
+            parse_with_quote_styles(r#"{ a = 'one'; "b" = "two"; c = three; 'd' = 'it\'s'; }"#).unwrap();
+        assert_eq!(value.get("d").and_then(|v| v.as_str()), Some("it's"));
+        assert_eq!(styles.get(&QuoteSite::value(&["a"])), Some(&QuoteStyle::Single));
+        assert_eq!(styles.get(&QuoteSite::key(&["a"])), None);
+        assert_eq!(styles.get(&QuoteSite::key(&["b"])), Some(&QuoteStyle::Double));
+        assert_eq!(styles.get(&QuoteSite::value(&["b"])), Some(&QuoteStyle::Double));
+        assert_eq!(styles.get(&QuoteSite::key(&["d"])), Some(&QuoteStyle::Single));
+        assert_eq!(styles.get(&QuoteSite::value(&["d"])), Some(&QuoteStyle::Single));
+        assert_eq!(styles.get(&QuoteSite::value(&["c"])), None);
+        assert_eq!(styles.len(), 5);
+    }
+
+    #[test]
+    fn test_quote_styles_are_recorded_per_occurrence() {
+        let (_, styles) =
+            parse_with_quote_styles(r#"{ a = Foo; b = 'Foo'; list = ( "x", x, { k = 'x'; } ); }"#).unwrap();
+        assert_eq!(styles.get(&QuoteSite::value(&["a"])), None);
+        assert_eq!(styles.get(&QuoteSite::value(&["b"])), Some(&QuoteStyle::Single));
+        assert_eq!(styles.get(&QuoteSite::value(&["list", "0"])), Some(&QuoteStyle::Double));
+        assert_eq!(styles.get(&QuoteSite::value(&["list", "1"])), None);
+        assert_eq!(
+            styles.get(&QuoteSite::value(&["list", "2", "k"])),
+            Some(&QuoteStyle::Single)
+        );
+    }
+
+    #[test]
+    fn test_parse_bounded() {
+        let input = "{ archiveVersion = 1; objects = { A = { isa = PBXGroup; children = (B); }; B = { isa = PBXFileReference; }; }; rootObject = A; }";
+        let generous = ParseLimits {
+            max_bytes: 1024,
+            max_objects: 2,
+            max_depth: 4,
+            max_data_bytes: 16,
+        };
+        let value = parse_bounded(input, generous).unwrap();
+        assert_eq!(
+            value.get("objects").and_then(|v| v.as_object()).map(|o| o.len()),
+            Some(2)
+        );
+        assert!(parse_bounded(input, ParseLimits::default()).is_ok());
+
+        let err = parse_bounded(
+            input,
+            ParseLimits {
+                max_bytes: 64,
+                ..generous
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("exceeds the limit of 64 bytes"), "{}", err);
+
+        let err = parse_bounded(
+            input,
+            ParseLimits {
+                max_objects: 1,
+                ..generous
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("Maximum object count of 1"), "{}", err);
+
+        let err = parse_bounded(
+            input,
+            ParseLimits {
+                max_depth: 3,
+                ..generous
+            },
+        )
+        .unwrap_err();
+        assert!(err.contains("Maximum nesting depth of 3"), "{}", err);
+
+        // Only entries of the root `objects` dictionary count towards the limit.
+        let nested = "{ objects = { A = { x = { y = 1; z = 2; }; }; }; other = { a = 1; b = 2; }; }";
+        assert!(parse_bounded(
+            nested,
+            ParseLimits {
+                max_objects: 1,
+                ..generous
+            }
+        )
+        .is_ok());
+    }
+
+    /// Small xorshift generator so the fuzz tests stay deterministic without a `rand` dependency.
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn test_random_bytes_never_panic() {
+        const ALPHABET: &[u8] = b"{}()<>=;,\"'/*\\ \nUaz09.-_$\xc3\xa9";
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..2000 {
+            let len = (xorshift(&mut state) % 64) as usize;
+            let bytes: Vec<u8> = (0..len)
+                .map(|_| {
+                    let r = xorshift(&mut state);
+                    if r % 4 == 0 {
+                        r as u8
+                    } else {
+                        ALPHABET[(r >> 8) as usize % ALPHABET.len()]
+                    }
+                })
+                .collect();
+            let text = String::from_utf8_lossy(&bytes);
+            let _ = parse_bounded(&text, ParseLimits::default());
+            let _ = crate::parser::lexer::Lexer::new(&text).tokenize_all();
+            let _ = parse_untrusted(&text, ParseLimits::default());
+        }
+    }
+
+    #[test]
+    fn test_mutated_fixture_never_panics() {
+        let original = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/project.pbxproj")).unwrap();
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..300 {
+            let mut bytes = original.clone();
+            for _ in 0..8 {
+                let r = xorshift(&mut state);
+                let at = (r >> 16) as usize % bytes.len();
+                const INSERTS: &[u8] = b"{(\"\\<";
+                match r % 8 {
+                    0 => bytes.truncate(at.max(1)),
+                    1 | 2 => bytes.insert(at, INSERTS[(r >> 8) as usize % INSERTS.len()]),
+                    _ => bytes[at] = r as u8,
+                }
+            }
+            let text = String::from_utf8_lossy(&bytes);
+            let _ = parse_bounded(&text, ParseLimits::default());
+            let _ = crate::parser::lexer::Lexer::new(&text).tokenize_all();
+        }
+    }
+
+    #[test]
+    fn test_parse_untrusted_applies_limits() {
+        let limits = ParseLimits {
+            max_bytes: 8,
+            ..ParseLimits::default()
+        };
+        assert!(parse_untrusted("{ a = b; c = d; }", limits).is_err());
+        assert!(parse_untrusted("{ a = b; }", ParseLimits::default()).is_ok());
+        assert!(parse_untrusted("{ a = \"\\é\"; }", ParseLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_data_literal_size_limit() {
+        let input = format!("{{ icon = <{}>; }}", "0a1b ".repeat(100));
+        let limits = ParseLimits {
+            max_data_bytes: 64,
+            ..ParseLimits::default()
+        };
+        let err = parse_bounded(&input, limits).unwrap_err();
+        assert!(err.contains("Data literal of 200 bytes"), "{}", err);
+        assert!(err.contains("limit of 64 bytes"), "{}", err);
+
+        let limits = ParseLimits {
+            max_data_bytes: 200,
+            ..ParseLimits::default()
+        };
+        match parse_bounded(&input, limits).unwrap().get("icon") {
+            Some(PlistValue::Data(bytes)) => assert_eq!(bytes.len(), 200),
+            other => panic!("expected data, got {:?}", other),
+        }
+
+        // An odd trailing digit decodes as its own byte, as before.
+        assert_eq!(decode_data_literal(b"0a1 b", usize::MAX, 0).unwrap(), vec![0x0a, 0x1b]);
+        assert_eq!(decode_data_literal(b"abc", usize::MAX, 0).unwrap(), vec![0xab, 0x0c]);
+    }
 }
@@ -16,6 +16,52 @@ pub fn build_plist(value: &serde_json::Value) -> Result<String, String> {
     String::from_utf8(buf).map_err(|e| format!("Plist output is not valid UTF-8: {}", e))
 }
 
+/// Read a settings sidecar such as `WorkspaceSettings.xcsettings` or
+/// `xcschememanagement.plist`, XML or binary. The root must be a dictionary.
+pub fn parse_settings(path: &str) -> Result<serde_json::Value, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let value: serde_json::Value =
+        plist::from_reader(Cursor::new(bytes)).map_err(|e| format!("Failed to parse plist: {}", e))?;
+    if !value.is_object() {
+        return Err(format!("Settings plist root must be a dictionary: {}", path));
+    }
+    Ok(value)
+}
+
+/// Write a settings dictionary to `path` as an XML plist, the format Xcode writes them in.
+pub fn write_settings(path: &str, value: &serde_json::Value) -> Result<(), String> {
+    if !value.is_object() {
+        return Err("Settings plist root must be a dictionary".to_string());
+    }
+    std::fs::write(path, build_plist(value)?).map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+/// Schemes hidden in Xcode's scheme list: `SchemeUserState` entries of an
+/// `xcschememanagement.plist` with `isShown = false`, e.g. `App.xcscheme_^#shared#^_`.
+pub fn get_suppressed_schemes(settings: &serde_json::Value) -> Vec<String> {
+    settings
+        .get("SchemeUserState")
+        .and_then(|v| v.as_object())
+        .map(|schemes| {
+            schemes
+                .iter()
+                .filter(|(_, state)| state.get("isShown").and_then(|v| v.as_bool()) == Some(false))
+                .map(|(name, _)| name.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Target UUIDs listed under `SuppressBuildableAutocreation` in an
+/// `xcschememanagement.plist`, for which Xcode won't create schemes automatically.
+pub fn get_autocreation_suppressed_targets(settings: &serde_json::Value) -> Vec<String> {
+    settings
+        .get("SuppressBuildableAutocreation")
+        .and_then(|v| v.as_object())
+        .map(|targets| targets.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +177,72 @@ mod tests {
         let result = parse_plist("not xml at all");
         assert!(result.is_err());
     }
+
+    const SCHEME_MANAGEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>SchemeUserState</key>
+	<dict>
+		<key>App.xcscheme_^#shared#^_</key>
+		<dict>
+			<key>orderHint</key>
+			<integer>0</integer>
+		</dict>
+		<key>Widget.xcscheme_^#shared#^_</key>
+		<dict>
+			<key>isShown</key>
+			<false/>
+			<key>orderHint</key>
+			<integer>1</integer>
+		</dict>
+	</dict>
+	<key>SuppressBuildableAutocreation</key>
+	<dict>
+		<key>13B07F861A680F5B00A75B9A</key>
+		<dict>
+			<key>primary</key>
+			<true/>
+		</dict>
+	</dict>
+</dict>
+</plist>"#;
+
+    #[test]
+    fn test_scheme_management_round_trip() {
+        let dir = std::env::temp_dir().join(format!("xcode-settings-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("xcschememanagement.plist");
+        std::fs::write(&path, SCHEME_MANAGEMENT).unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let settings = parse_settings(&path).unwrap();
+        assert_eq!(get_suppressed_schemes(&settings), vec!["Widget.xcscheme_^#shared#^_"]);
+        assert_eq!(
+            get_autocreation_suppressed_targets(&settings),
+            vec!["13B07F861A680F5B00A75B9A"]
+        );
+
+        let copy = dir.join("copy.plist").to_string_lossy().to_string();
+        write_settings(&copy, &settings).unwrap();
+        assert_eq!(parse_settings(&copy).unwrap(), settings);
+
+        assert!(write_settings(&copy, &serde_json::json!([1, 2])).is_err());
+        assert!(parse_settings(&dir.join("missing.plist").to_string_lossy()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_numeric_character_references_are_decoded() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>com.apple.developer.team-name</key>
+	<string>Caf&#233; &#x2603;</string>
+</dict>
+</plist>"#;
+        let value = parse_plist(xml).unwrap();
+        assert_eq!(value["com.apple.developer.team-name"], "Café ☃");
+    }
 }
@@ -51,7 +51,7 @@ where
                 let parts: Vec<&str> = inner.splitn(2, ':').collect();
                 let variable = parts[0];
                 let transformations: Vec<&str> = if parts.len() > 1 {
-                    parts[1].split(':').collect()
+                    split_transformations(parts[1])
                 } else {
                     vec![]
                 };
@@ -89,6 +89,55 @@ where
     result
 }
 
+/// Transform names that take no argument. Anything after `default=` runs until
+/// the next `:` that introduces one of these.
+const TRANSFORMS: &[&str] = &[
+    "lower",
+    "upper",
+    "suffix",
+    "file",
+    "dir",
+    "base",
+    "rfc1034identifier",
+    "c99extidentifier",
+    "standardizepath",
+];
+
+/// Split a transformation list like `lower:default=/usr/bin` on `:`.
+///
+/// Only `default=` takes an argument, and that argument may itself contain `:`
+/// (URLs, nested `$(VAR:transform)` references), so a `:` only starts a new
+/// transformation outside of parentheses and — inside a `default=` value — only
+/// when it's followed by a known transform name.
+fn split_transformations(list: &str) -> Vec<&str> {
+    let bytes = list.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' if depth > 0 => depth -= 1,
+            b':' if depth == 0 => {
+                let current = &list[start..i];
+                if current.starts_with("default=") {
+                    let rest = &list[i + 1..];
+                    let next = rest.split(':').next().unwrap_or("");
+                    if !TRANSFORMS.contains(&next) && !next.starts_with("default=") {
+                        continue;
+                    }
+                }
+                parts.push(current);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&list[start..]);
+    parts
+}
+
 fn apply_transform(value: &str, modifier: &str) -> String {
     match modifier {
         "lower" => value.to_lowercase(),
@@ -113,21 +162,18 @@ fn apply_transform(value: &str, modifier: &str) -> String {
             .chars()
             .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
             .collect(),
-        "c99extidentifier" => value
-            .chars()
-            .map(|c| if c == '-' || c == ' ' { '_' } else { c })
-            .collect(),
-        "standardizepath" => {
-            if value.is_empty() {
-                String::new()
-            } else {
-                // Approximate: resolve the path
-                let path = Path::new(value);
-                path.canonicalize()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .unwrap_or_else(|_| value.to_string())
+        "c99extidentifier" => {
+            let mut ident: String = value
+                .chars()
+                .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+                .collect();
+            // C identifiers can't start with a digit — Xcode prefixes an underscore
+            if ident.starts_with(|c: char| c.is_ascii_digit()) {
+                ident.insert(0, '_');
             }
+            ident
         }
+        "standardizepath" => standardize_path(value),
         other => {
             // Handle default=VALUE
             if let Some(default_val) = other.strip_prefix("default=") {
@@ -143,6 +189,42 @@ fn apply_transform(value: &str, modifier: &str) -> String {
     }
 }
 
+/// Lexically normalize a path: collapse `.`/`..` segments and redundant slashes.
+///
+/// Never touches the filesystem, so the result doesn't depend on the machine
+/// the project is being analyzed on.
+fn standardize_path(value: &str) -> String {
+    if value.is_empty() {
+        return String::new();
+    }
+
+    let is_absolute = value.starts_with('/');
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in value.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => match segments.last() {
+                Some(&last) if last != ".." => {
+                    segments.pop();
+                }
+                // `..` at the root of an absolute path stays at the root
+                _ if is_absolute => {}
+                _ => segments.push(".."),
+            },
+            other => segments.push(other),
+        }
+    }
+
+    let joined = segments.join("/");
+    if is_absolute {
+        format!("/{}", joined)
+    } else if joined.is_empty() {
+        ".".to_string()
+    } else {
+        joined
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,4 +299,91 @@ mod tests {
         let result = resolve_xcode_build_setting("$(FILE:suffix)", &|key| vars.get(key).cloned());
         assert_eq!(result, ".swift");
     }
+
+    #[test]
+    fn test_transform_c99extidentifier() {
+        let mut vars = HashMap::new();
+        vars.insert("PRODUCT_NAME".to_string(), "My App-Name".to_string());
+
+        let result = resolve_xcode_build_setting("$(PRODUCT_NAME:c99extidentifier)", &|key| vars.get(key).cloned());
+        assert_eq!(result, "My_App_Name");
+    }
+
+    #[test]
+    fn test_transform_c99extidentifier_leading_digit() {
+        let mut vars = HashMap::new();
+        vars.insert("PRODUCT_NAME".to_string(), "123abc".to_string());
+
+        let result = resolve_xcode_build_setting("$(PRODUCT_NAME:c99extidentifier)", &|key| vars.get(key).cloned());
+        assert_eq!(result, "_123abc");
+    }
+
+    #[test]
+    fn test_transform_c99extidentifier_punctuation() {
+        let mut vars = HashMap::new();
+        vars.insert("PRODUCT_NAME".to_string(), "my.name".to_string());
+
+        let result = resolve_xcode_build_setting("$(PRODUCT_NAME:c99extidentifier)", &|key| vars.get(key).cloned());
+        assert_eq!(result, "my_name");
+    }
+
+    #[test]
+    fn test_transform_standardizepath() {
+        let mut vars = HashMap::new();
+        vars.insert("DIR".to_string(), "a/./b/../c".to_string());
+
+        let result = resolve_xcode_build_setting("$(DIR:standardizepath)", &|key| vars.get(key).cloned());
+        assert_eq!(result, "a/c");
+    }
+
+    #[test]
+    fn test_standardize_path_absolute() {
+        assert_eq!(standardize_path("/usr//local/./bin/"), "/usr/local/bin");
+        assert_eq!(standardize_path("/usr/../../bin"), "/bin");
+        assert_eq!(standardize_path("/"), "/");
+        // Nonexistent paths are normalized, not passed through
+        assert_eq!(standardize_path("/does/not/exist/../here"), "/does/not/here");
+    }
+
+    #[test]
+    fn test_standardize_path_relative() {
+        assert_eq!(standardize_path("../a/b/.."), "../a");
+        assert_eq!(standardize_path("a/.."), ".");
+        assert_eq!(standardize_path(""), "");
+    }
+
+    #[test]
+    fn test_transform_default_path() {
+        let result = resolve_xcode_build_setting("$(TOOL:default=/usr/bin)", &|_| None);
+        assert_eq!(result, "/usr/bin");
+    }
+
+    #[test]
+    fn test_transform_default_with_colon() {
+        let result = resolve_xcode_build_setting("$(HOST:default=http://localhost:8080)", &|_| None);
+        assert_eq!(result, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_transform_default_then_transform() {
+        let result = resolve_xcode_build_setting("$(NAME:default=My App:c99extidentifier)", &|_| None);
+        assert_eq!(result, "My_App");
+    }
+
+    #[test]
+    fn test_transform_chain() {
+        let mut vars = HashMap::new();
+        vars.insert("PRODUCT_NAME".to_string(), "My-App".to_string());
+
+        let result =
+            resolve_xcode_build_setting("$(PRODUCT_NAME:lower:c99extidentifier)", &|key| vars.get(key).cloned());
+        assert_eq!(result, "my_app");
+    }
+
+    #[test]
+    fn test_split_transformations() {
+        assert_eq!(split_transformations("lower:upper"), vec!["lower", "upper"]);
+        assert_eq!(split_transformations("default=a:b:lower"), vec!["default=a:b", "lower"]);
+        assert_eq!(split_transformations("default=$(X:lower)"), vec!["default=$(X:lower)"]);
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use crate::objects::PbxObject;
 
 use super::xcode_project::XcodeProject;
@@ -5,8 +7,14 @@ use super::xcode_project::XcodeProject;
 /// Resolve the real filesystem path for a file reference or group.
 ///
 /// Port of `getRealPath` from `paths.ts`.
+///
+/// If the group ancestry loops back on itself, resolution stops at the repeated group.
 pub fn get_real_path(project: &XcodeProject, object: &PbxObject) -> Option<String> {
-    let source_tree_path = get_source_tree_real_path(project, object)?;
+    real_path(project, object, &mut HashSet::new())
+}
+
+fn real_path(project: &XcodeProject, object: &PbxObject, visited: &mut HashSet<String>) -> Option<String> {
+    let source_tree_path = source_tree_real_path(project, object, visited)?;
     let path = object.get_str("path").unwrap_or("");
     if source_tree_path.is_empty() && path.is_empty() {
         return None;
@@ -24,6 +32,13 @@ pub fn get_real_path(project: &XcodeProject, object: &PbxObject) -> Option<Strin
 ///
 /// Port of `getSourceTreeRealPath` from `paths.ts`.
 pub fn get_source_tree_real_path(project: &XcodeProject, object: &PbxObject) -> Option<String> {
+    source_tree_real_path(project, object, &mut HashSet::new())
+}
+
+fn source_tree_real_path(project: &XcodeProject, object: &PbxObject, visited: &mut HashSet<String>) -> Option<String> {
+    if !visited.insert(object.uuid.clone()) {
+        return None;
+    }
     let source_tree = object.get_str("sourceTree")?;
 
     match source_tree {
@@ -40,10 +55,11 @@ pub fn get_source_tree_real_path(project: &XcodeProject, object: &PbxObject) ->
                     Some(format!("{}/{}", project_root, project_dir))
                 }
             } else {
-                get_real_path(project, &parent)
+                // An empty parent path (no project root) leaves the path group-relative.
+                Some(real_path(project, &parent, visited).unwrap_or_default())
             }
         }
-        "SOURCE_ROOT" => project.get_project_root(),
+        "SOURCE_ROOT" => Some(project.get_project_root().unwrap_or_default()),
         "<absolute>" => Some(String::new()),
         // Other source trees like SDKROOT, BUILT_PRODUCTS_DIR, etc.
         other => Some(other.to_string()),
@@ -54,7 +70,14 @@ pub fn get_source_tree_real_path(project: &XcodeProject, object: &PbxObject) ->
 ///
 /// Port of `getFullPath` from `paths.ts`.
 pub fn get_full_path(project: &XcodeProject, object: &PbxObject) -> Option<String> {
-    let root_path = get_resolved_root_path(project, object);
+    full_path(project, object, &mut HashSet::new())
+}
+
+fn full_path(project: &XcodeProject, object: &PbxObject, visited: &mut HashSet<String>) -> Option<String> {
+    if !visited.insert(object.uuid.clone()) {
+        return None;
+    }
+    let root_path = resolved_root_path(project, object, visited);
     let path = object.get_str("path").unwrap_or("");
 
     if path.is_empty() {
@@ -70,7 +93,7 @@ pub fn get_full_path(project: &XcodeProject, object: &PbxObject) -> Option<Strin
     }
 }
 
-fn get_resolved_root_path(project: &XcodeProject, object: &PbxObject) -> Option<String> {
+fn resolved_root_path(project: &XcodeProject, object: &PbxObject, visited: &mut HashSet<String>) -> Option<String> {
     let source_tree = object.get_str("sourceTree")?;
 
     match source_tree {
@@ -79,7 +102,7 @@ fn get_resolved_root_path(project: &XcodeProject, object: &PbxObject) -> Option<
             if parent.isa == "PBXProject" {
                 Some(String::new())
             } else {
-                get_full_path(project, &parent)
+                full_path(project, &parent, visited)
             }
         }
         "SOURCE_ROOT" => Some(String::new()),
@@ -101,18 +124,24 @@ fn get_parent(project: &XcodeProject, object: &PbxObject) -> Option<PbxObject> {
     groups.first().map(|g| (**g).clone())
 }
 
-/// Get all parent groups up to the root.
+/// Get all parent groups up to the root, outermost first.
+///
+/// Stops at the first repeated group if the ancestry contains a cycle.
 pub fn get_parents(project: &XcodeProject, object: &PbxObject) -> Vec<PbxObject> {
     let main_group = project.main_group_uuid();
-    if main_group.as_deref() == Some(&object.uuid) {
-        return vec![];
-    }
-
-    if let Some(parent) = get_parent(project, object) {
-        let mut parents = get_parents(project, &parent);
-        parents.push(parent);
-        parents
-    } else {
-        vec![]
+    let mut visited = HashSet::from([object.uuid.clone()]);
+    let mut parents = Vec::new();
+    let mut current = object.clone();
+    while main_group.as_deref() != Some(&current.uuid) {
+        let Some(parent) = get_parent(project, &current) else {
+            break;
+        };
+        if !visited.insert(parent.uuid.clone()) {
+            break;
+        }
+        parents.push(parent.clone());
+        current = parent;
     }
+    parents.reverse();
+    parents
 }
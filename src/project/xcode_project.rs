@@ -1,14 +1,26 @@
 use std::borrow::Cow;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
 use indexmap::IndexMap;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde::Deserialize;
 
 use crate::objects::{PbxObject, PbxObjectExt};
 use crate::parser;
+use crate::types::constants::{
+    file_category, ConfigVariant, FileCategory, ProjectDefaultBuildSettings, BOOLEAN_BUILD_SETTINGS,
+    LAST_KNOWN_ARCHIVE_VERSION, LAST_KNOWN_OBJECT_VERSION, LAST_UPGRADE_CHECK, MINIMIZED_PROXIES_OBJECT_VERSION,
+    MIN_DEPLOYMENT_TARGETS, PRODUCT_UTI_EXTENSIONS, SOURCE_TREES, XCODE_16_OBJECT_VERSION,
+};
+use crate::types::isa::Isa;
 use crate::types::plist::{PlistMap, PlistObject, PlistValue};
-use crate::writer::serializer;
+use crate::writer::comments;
+use crate::writer::serializer::{self, WriterOptions};
 
+use super::build_settings::resolve_xcode_build_setting;
+use super::paths::{get_full_path, get_real_path};
 use super::uuid::generate_uuid;
 
 /// An orphaned reference: an object UUID referenced from a property
@@ -19,6 +31,174 @@ pub struct OrphanedReference {
     pub referrer_isa: String,
     pub property: String,
     pub orphan_uuid: String,
+    /// Whether the dangling UUID is an entry of an array property rather than its whole value.
+    pub in_array: bool,
+}
+
+/// How to repair an [`OrphanedReference`]; see [`OrphanedReference::suggested_action`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepairAction {
+    /// Drop the dangling UUID from the referrer's array property.
+    RemoveFromArray,
+    /// Remove the referrer's scalar property; the object stays valid without it.
+    ClearScalar,
+    /// The referrer is meaningless without the missing object (e.g. a `PBXBuildFile`
+    /// whose file reference was deleted), so remove the referrer as well.
+    RemoveReferrer,
+}
+
+impl RepairAction {
+    /// Stable camelCase name, as reported by the JS bindings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RemoveFromArray => "removeFromArray",
+            Self::ClearScalar => "clearScalar",
+            Self::RemoveReferrer => "removeReferrer",
+        }
+    }
+}
+
+impl OrphanedReference {
+    /// Classify the likely fix: array entries are dropped, and scalar references are
+    /// cleared unless the referrer's ISA can't do without the property.
+    pub fn suggested_action(&self) -> RepairAction {
+        if self.in_array {
+            return RepairAction::RemoveFromArray;
+        }
+        match (self.referrer_isa.as_str(), self.property.as_str()) {
+            ("PBXBuildFile", "fileRef" | "productRef")
+            | ("PBXTargetDependency", "target" | "targetProxy")
+            | ("PBXContainerItemProxy", "containerPortal")
+            | ("PBXReferenceProxy", "remoteRef") => RepairAction::RemoveReferrer,
+            _ => RepairAction::ClearScalar,
+        }
+    }
+}
+
+/// A build setting Xcode would reject or silently misinterpret.
+#[derive(Debug, Clone)]
+pub struct SettingWarning {
+    /// Target name, or `None` for project-level configurations.
+    pub target: Option<String>,
+    pub config: String,
+    pub key: String,
+    pub message: String,
+}
+
+/// Overrides for [`XcodeProject::add_file_with_options`]. `None` fields are inferred from the path.
+#[derive(Debug, Clone, Default)]
+pub struct FileOptions {
+    pub source_tree: Option<String>,
+    pub file_type: Option<String>,
+    pub name: Option<String>,
+}
+
+/// Objects wired up by [`XcodeProject::embed_extension`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtensionEmbedding {
+    /// The `PBXCopyFilesBuildPhase` added to the host target.
+    pub phase_uuid: String,
+    /// The `PBXBuildFile` copying the extension's product.
+    pub build_file_uuid: String,
+    /// The host's `PBXTargetDependency` on the extension, new or pre-existing.
+    pub dependency_uuid: String,
+}
+
+/// A target embedded in a host through a copy-files phase, as returned by
+/// [`XcodeProject::embedded_extensions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedExtension {
+    pub target_uuid: String,
+    pub phase_uuid: String,
+    /// Destination of the copy-files phase, e.g. 13 (PlugIns) or 16 (Products Directory).
+    pub dst_subfolder_spec: Option<i64>,
+    /// The phase's name, e.g. "Embed App Clips" or "Embed Foundation Extensions".
+    pub phase_name: Option<String>,
+}
+
+/// Whether a Swift package is fetched from a repository or lives on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwiftPackageKind {
+    Remote,
+    Local,
+}
+
+/// Version rule from an `XCRemoteSwiftPackageReference`'s `requirement` dictionary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwiftPackageRequirement {
+    UpToNextMajorVersion(String),
+    UpToNextMinorVersion(String),
+    VersionRange { minimum: String, maximum: String },
+    ExactVersion(String),
+    Branch(String),
+    Revision(String),
+}
+
+impl SwiftPackageRequirement {
+    /// Read a `requirement` dictionary. Returns `None` for kinds not modelled here.
+    pub fn from_plist(value: &PlistValue) -> Option<Self> {
+        let field = |key: &str| value.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        match value.get("kind")?.as_str()? {
+            "upToNextMajorVersion" => Some(Self::UpToNextMajorVersion(field("minimumVersion")?)),
+            "upToNextMinorVersion" => Some(Self::UpToNextMinorVersion(field("minimumVersion")?)),
+            "versionRange" => Some(Self::VersionRange {
+                minimum: field("minimumVersion")?,
+                maximum: field("maximumVersion")?,
+            }),
+            "exactVersion" => Some(Self::ExactVersion(field("version")?)),
+            "branch" => Some(Self::Branch(field("branch")?)),
+            "revision" => Some(Self::Revision(field("revision")?)),
+            _ => None,
+        }
+    }
+
+    /// Build the `requirement` dictionary, with keys in the alphabetical order Xcode writes.
+    pub fn to_plist(&self) -> PlistValue<'static> {
+        let entry = |key: &str, value: &str| {
+            (
+                Cow::Owned(key.to_string()),
+                PlistValue::String(Cow::Owned(value.to_string())),
+            )
+        };
+        let pairs = match self {
+            Self::UpToNextMajorVersion(min) => {
+                vec![entry("kind", "upToNextMajorVersion"), entry("minimumVersion", min)]
+            }
+            Self::UpToNextMinorVersion(min) => {
+                vec![entry("kind", "upToNextMinorVersion"), entry("minimumVersion", min)]
+            }
+            Self::VersionRange { minimum, maximum } => vec![
+                entry("kind", "versionRange"),
+                entry("maximumVersion", maximum),
+                entry("minimumVersion", minimum),
+            ],
+            Self::ExactVersion(version) => vec![entry("kind", "exactVersion"), entry("version", version)],
+            Self::Branch(branch) => vec![entry("branch", branch), entry("kind", "branch")],
+            Self::Revision(revision) => vec![entry("kind", "revision"), entry("revision", revision)],
+        };
+        PlistValue::Object(pairs)
+    }
+}
+
+/// A package product linked into targets through an `XCSwiftPackageProductDependency`.
+#[derive(Debug, Clone)]
+pub struct SwiftPackageProduct {
+    pub uuid: String,
+    pub product_name: String,
+    /// UUIDs of targets listing this product in `packageProductDependencies`.
+    pub targets: Vec<String>,
+}
+
+/// A Swift package reference with its products, as returned by [`XcodeProject::swift_packages`].
+#[derive(Debug, Clone)]
+pub struct SwiftPackageInfo {
+    pub uuid: String,
+    pub kind: SwiftPackageKind,
+    /// `repositoryURL` for remote packages, `relativePath` for local ones.
+    pub location: String,
+    /// Always `None` for local packages.
+    pub requirement: Option<SwiftPackageRequirement>,
+    pub products: Vec<SwiftPackageProduct>,
 }
 
 /// The main container for an Xcode project.
@@ -26,14 +206,23 @@ pub struct OrphanedReference {
 /// Stores all objects as a flat map of UUID → PbxObject, plus project metadata.
 /// Unlike the TypeScript version, this does NOT inflate UUID references into
 /// object pointers. All references are UUID strings, lookups go through this map.
+///
+/// The map is shared copy-on-write: cloning a project is cheap, and the objects are
+/// only copied when one of the clones is first mutated.
 #[derive(Debug, Clone)]
 pub struct XcodeProject {
     pub archive_version: i64,
     pub object_version: i64,
     pub classes: PlistObject<'static>,
     pub root_object_uuid: String,
-    objects: IndexMap<String, PbxObject>,
+    objects: Arc<IndexMap<String, PbxObject>>,
     file_path: Option<String>,
+    /// Project root to resolve paths against when there's no `file_path` (e.g. in the browser).
+    base_path: Option<String>,
+    /// Byte length of the text this project was parsed from (0 if built in memory).
+    original_len: usize,
+    /// Reference-bearing keys registered per ISA on top of [`PbxObject::reference_keys`].
+    extra_reference_keys: HashMap<String, Vec<String>>,
 }
 
 impl XcodeProject {
@@ -45,14 +234,45 @@ impl XcodeProject {
         Ok(project)
     }
 
+    /// Open a project from an `.xcodeproj` bundle directory.
+    ///
+    /// Reads `<dir>/project.pbxproj`. Paths to the `project.pbxproj` file itself
+    /// are accepted too, so this works with either form.
+    pub fn open_xcodeproj(dir: &str) -> Result<Self, String> {
+        let path = Path::new(dir);
+        if path.is_dir() {
+            Self::open(&path.join("project.pbxproj").to_string_lossy())
+        } else {
+            Self::open(dir)
+        }
+    }
+
     /// Parse a .pbxproj string into an XcodeProject.
     pub fn from_plist(text: &str) -> Result<Self, String> {
         let plist = parser::parse(text)?.into_owned();
+        let mut project = Self::from_plist_value(&plist)?;
+        project.original_len = text.len();
+        Ok(project)
+    }
+
+    /// Create from the JSON representation produced by [`to_json`](Self::to_json).
+    pub fn from_json(value: &serde_json::Value) -> Result<Self, String> {
+        let plist = PlistValue::deserialize(value).map_err(|e| format!("Invalid project JSON: {}", e))?;
+        Self::from_plist_value(&plist)
+    }
+
+    /// Create from a JSON string, as produced by [`to_json_string`](Self::to_json_string).
+    pub fn from_json_str(text: &str) -> Result<Self, String> {
+        let plist: PlistValue<'static> =
+            serde_json::from_str(text).map_err(|e| format!("Invalid project JSON: {}", e))?;
         Self::from_plist_value(&plist)
     }
 
     /// Create from an already-parsed PlistValue.
     pub fn from_plist_value(plist: &PlistValue<'static>) -> Result<Self, String> {
+        if plist.as_array().is_some() {
+            return Err("Root must be an object, got an array".to_string());
+        }
         plist.as_object().ok_or("Root must be an object")?;
 
         let archive_version = plist.get("archiveVersion").and_then(|v| v.as_integer()).unwrap_or(1);
@@ -76,8 +296,8 @@ impl XcodeProject {
             .and_then(|v| v.as_object())
             .ok_or("objects is required")?;
 
-        // Inflate all objects
-        let mut objects = IndexMap::new();
+        // Inflate all objects (preallocated: large projects have thousands)
+        let mut objects = IndexMap::with_capacity(objects_pairs.len());
         for (uuid, obj_plist) in objects_pairs {
             if let Some(obj_pairs) = obj_plist.as_object() {
                 let obj = PbxObject::from_plist(uuid.to_string(), obj_pairs);
@@ -102,15 +322,201 @@ impl XcodeProject {
             object_version,
             classes,
             root_object_uuid,
-            objects,
+            objects: Arc::new(objects),
             file_path: None,
+            base_path: None,
+            original_len: 0,
+            extra_reference_keys: HashMap::new(),
         })
     }
 
+    /// Create a minimal project from scratch: a `PBXProject` with a main group,
+    /// a Products group, and Debug/Release configurations using the default project settings.
+    pub fn new_empty(name: &str, organization: Option<&str>) -> Self {
+        let mut project = XcodeProject {
+            archive_version: LAST_KNOWN_ARCHIVE_VERSION,
+            object_version: LAST_KNOWN_OBJECT_VERSION,
+            classes: Vec::new(),
+            root_object_uuid: String::new(),
+            objects: Arc::default(),
+            file_path: None,
+            base_path: None,
+            original_len: 0,
+            extra_reference_keys: HashMap::new(),
+        };
+
+        let mut products_props = PlistMap::default();
+        products_props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXGroup".to_string())),
+        );
+        products_props.insert(Cow::Owned("children".to_string()), PlistValue::Array(vec![]));
+        products_props.insert(
+            Cow::Owned("name".to_string()),
+            PlistValue::String(Cow::Owned("Products".to_string())),
+        );
+        products_props.insert(
+            Cow::Owned("sourceTree".to_string()),
+            PlistValue::String(Cow::Owned("<group>".to_string())),
+        );
+        let products_uuid = project.create_object(products_props);
+
+        let mut main_props = PlistMap::default();
+        main_props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXGroup".to_string())),
+        );
+        main_props.insert(
+            Cow::Owned("children".to_string()),
+            PlistValue::Array(vec![PlistValue::String(Cow::Owned(products_uuid.clone()))]),
+        );
+        main_props.insert(
+            Cow::Owned("sourceTree".to_string()),
+            PlistValue::String(Cow::Owned("<group>".to_string())),
+        );
+        let main_group_uuid = project.create_object(main_props);
+
+        let mut config_uuids = Vec::new();
+        for (config_name, variant) in [("Debug", ConfigVariant::Debug), ("Release", ConfigVariant::Release)] {
+            let mut config_props = PlistMap::default();
+            config_props.insert(
+                Cow::Owned("isa".to_string()),
+                PlistValue::String(Cow::Owned("XCBuildConfiguration".to_string())),
+            );
+            config_props.insert(Cow::Owned("buildSettings".to_string()), PlistValue::Object(Vec::new()));
+            config_props.insert(
+                Cow::Owned("name".to_string()),
+                PlistValue::String(Cow::Owned(config_name.to_string())),
+            );
+            let config_uuid = project.create_object(config_props);
+            project.apply_default_settings(&config_uuid, variant);
+            config_uuids.push(PlistValue::String(Cow::Owned(config_uuid)));
+        }
+
+        let mut config_list_props = PlistMap::default();
+        config_list_props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("XCConfigurationList".to_string())),
+        );
+        config_list_props.insert(
+            Cow::Owned("buildConfigurations".to_string()),
+            PlistValue::Array(config_uuids),
+        );
+        config_list_props.insert(
+            Cow::Owned("defaultConfigurationIsVisible".to_string()),
+            PlistValue::Integer(0),
+        );
+        config_list_props.insert(
+            Cow::Owned("defaultConfigurationName".to_string()),
+            PlistValue::String(Cow::Owned("Release".to_string())),
+        );
+        let config_list_uuid = project.create_object(config_list_props);
+
+        let mut attributes: PlistObject<'static> = vec![
+            (
+                Cow::Owned("LastSwiftUpdateCheck".to_string()),
+                PlistValue::String(Cow::Owned(LAST_UPGRADE_CHECK.to_string())),
+            ),
+            (
+                Cow::Owned("LastUpgradeCheck".to_string()),
+                PlistValue::String(Cow::Owned(LAST_UPGRADE_CHECK.to_string())),
+            ),
+        ];
+        if let Some(org) = organization {
+            attributes.push((
+                Cow::Owned("ORGANIZATIONNAME".to_string()),
+                PlistValue::String(Cow::Owned(org.to_string())),
+            ));
+        }
+
+        let mut root_props = PlistMap::default();
+        root_props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXProject".to_string())),
+        );
+        root_props.insert(Cow::Owned("attributes".to_string()), PlistValue::Object(attributes));
+        root_props.insert(
+            Cow::Owned("buildConfigurationList".to_string()),
+            PlistValue::String(Cow::Owned(config_list_uuid)),
+        );
+        root_props.insert(
+            Cow::Owned("developmentRegion".to_string()),
+            PlistValue::String(Cow::Owned("en".to_string())),
+        );
+        root_props.insert(Cow::Owned("hasScannedForEncodings".to_string()), PlistValue::Integer(0));
+        root_props.insert(
+            Cow::Owned("knownRegions".to_string()),
+            PlistValue::Array(vec![
+                PlistValue::String(Cow::Owned("en".to_string())),
+                PlistValue::String(Cow::Owned("Base".to_string())),
+            ]),
+        );
+        root_props.insert(
+            Cow::Owned("mainGroup".to_string()),
+            PlistValue::String(Cow::Owned(main_group_uuid)),
+        );
+        root_props.insert(
+            Cow::Owned("productRefGroup".to_string()),
+            PlistValue::String(Cow::Owned(products_uuid)),
+        );
+        root_props.insert(
+            Cow::Owned("projectDirPath".to_string()),
+            PlistValue::String(Cow::Owned(String::new())),
+        );
+        root_props.insert(
+            Cow::Owned("projectRoot".to_string()),
+            PlistValue::String(Cow::Owned(String::new())),
+        );
+        root_props.insert(Cow::Owned("targets".to_string()), PlistValue::Array(vec![]));
+        // Seed with the project name so differently-named projects get different root UUIDs.
+        let seed = format!("PBXProject {}", name);
+        let root_uuid = project.get_unique_id(&seed);
+        let pairs: PlistObject<'static> = root_props.into_iter().collect();
+        project
+            .storage_mut()
+            .insert(root_uuid.clone(), PbxObject::from_plist(root_uuid.clone(), &pairs));
+        project.root_object_uuid = root_uuid;
+        project.sync_object_version_keys(false);
+
+        project
+    }
+
+    /// Change the project's `objectVersion`, adding or removing the `PBXProject`
+    /// keys that depend on it (`preferredProjectObjectVersion` from Xcode 16's format,
+    /// `minimizedProjectReferenceProxies` from version 77).
+    pub fn set_object_version(&mut self, version: i64) {
+        self.object_version = version;
+        self.sync_object_version_keys(true);
+    }
+
+    /// Add the version-dependent `PBXProject` keys the current `objectVersion` needs.
+    /// With `migrate`, also overwrite `preferredProjectObjectVersion` and drop keys
+    /// the version doesn't support; otherwise existing values are left alone.
+    fn sync_object_version_keys(&mut self, migrate: bool) {
+        let version = self.object_version;
+        let Some(root) = self.root_object_mut() else {
+            return;
+        };
+        if version >= XCODE_16_OBJECT_VERSION {
+            if migrate || !root.props.contains_key("preferredProjectObjectVersion") {
+                root.set_sorted("preferredProjectObjectVersion", PlistValue::Integer(version));
+            }
+        } else if migrate {
+            root.remove("preferredProjectObjectVersion");
+        }
+        if version >= MINIMIZED_PROXIES_OBJECT_VERSION {
+            if !root.props.contains_key("minimizedProjectReferenceProxies") {
+                root.set_sorted("minimizedProjectReferenceProxies", PlistValue::Integer(1));
+            }
+        } else if migrate {
+            root.remove("minimizedProjectReferenceProxies");
+        }
+    }
+
     /// Convert the project to a PlistValue for serialization.
     pub fn to_plist(&self) -> PlistValue<'static> {
-        let mut objects_pairs: PlistObject<'static> = Vec::new();
-        for (uuid, obj) in &self.objects {
+        let mut objects_pairs: PlistObject<'static> = Vec::with_capacity(self.objects.len());
+        for (uuid, obj) in self.objects.iter() {
             objects_pairs.push((Cow::Owned(uuid.clone()), PlistValue::Object(obj.to_plist())));
         }
 
@@ -133,17 +539,73 @@ impl XcodeProject {
         serializer::build(&self.to_plist())
     }
 
+    /// Serialize to .pbxproj format with custom writer options.
+    pub fn to_pbxproj_with_options(&self, options: WriterOptions) -> String {
+        serializer::Writer::with_options(&self.to_plist(), options).get_results()
+    }
+
     /// Serialize to JSON.
     pub fn to_json(&self) -> Result<serde_json::Value, String> {
         let plist = self.to_plist();
         serde_json::to_value(&plist).map_err(|e| e.to_string())
     }
 
+    /// Like [`to_json`](Self::to_json), but with every dictionary's keys sorted so that
+    /// equivalent projects produce identical JSON, e.g. for content-addressed storage.
+    ///
+    /// The `objects` map keeps its order, as it decides the order objects are written in.
+    pub fn to_json_sorted(&self) -> Result<serde_json::Value, String> {
+        let mut json = self.to_json()?;
+        if let serde_json::Value::Object(root) = &mut json {
+            root.sort_keys();
+            for (key, child) in root.iter_mut() {
+                match child {
+                    serde_json::Value::Object(objects) if key == "objects" => {
+                        objects.values_mut().for_each(sort_json_keys)
+                    }
+                    _ => sort_json_keys(child),
+                }
+            }
+        }
+        Ok(json)
+    }
+
+    /// Serialize to a JSON string without building an intermediate `serde_json::Value`.
+    pub fn to_json_string(&self, pretty: bool) -> Result<String, String> {
+        let plist = self.to_plist();
+        if pretty {
+            serde_json::to_string_pretty(&plist).map_err(|e| e.to_string())
+        } else {
+            serde_json::to_string(&plist).map_err(|e| e.to_string())
+        }
+    }
+
+    /// Stream the project as JSON to `writer`, with the same layout as [`to_json`](Self::to_json).
+    ///
+    /// Serializes straight from the object map, without building a `serde_json::Value`
+    /// or an intermediate copy of the project.
+    pub fn write_json<W: std::io::Write>(&self, writer: W, pretty: bool) -> Result<(), String> {
+        let view = JsonView(self);
+        if pretty {
+            serde_json::to_writer_pretty(writer, &view).map_err(|e| e.to_string())
+        } else {
+            serde_json::to_writer(writer, &view).map_err(|e| e.to_string())
+        }
+    }
+
     /// Write the project to its original file.
+    ///
+    /// The output goes to a sibling temp file that is then renamed over the original,
+    /// so a crash mid-write can't leave a truncated `project.pbxproj` behind.
     pub fn save(&self) -> Result<(), String> {
         let path = self.file_path.as_ref().ok_or("No file path set")?;
         let output = self.to_pbxproj();
-        std::fs::write(path, output).map_err(|e| e.to_string())
+        write_atomically(Path::new(path), &output)
+    }
+
+    /// Write the project to another path, leaving `file_path` unchanged. Atomic like [`save`](Self::save).
+    pub fn save_as(&self, path: &str) -> Result<(), String> {
+        write_atomically(Path::new(path), &self.to_pbxproj())
     }
 
     /// Get the file path this project was loaded from.
@@ -151,8 +613,29 @@ impl XcodeProject {
         self.file_path.as_deref()
     }
 
+    /// Byte length of the text this project was parsed from, or 0 if it was built in memory.
+    pub fn original_len(&self) -> usize {
+        self.original_len
+    }
+
+    /// How many bytes the serialized project has grown (or shrunk, if negative)
+    /// since it was parsed.
+    pub fn size_delta(&self) -> i64 {
+        self.to_pbxproj().len() as i64 - self.original_len as i64
+    }
+
+    /// Set the project root directory (the parent of `*.xcodeproj`) used for path
+    /// resolution. Overrides the root derived from `file_path`, which lets projects
+    /// parsed from a string resolve paths against a virtual root.
+    pub fn set_base_path(&mut self, path: &str) {
+        self.base_path = Some(path.trim_end_matches('/').to_string());
+    }
+
     /// Get the project root directory (parent of *.xcodeproj).
     pub fn get_project_root(&self) -> Option<String> {
+        if let Some(base) = &self.base_path {
+            return Some(base.clone());
+        }
         self.file_path.as_ref().map(|p| {
             Path::new(p)
                 .parent() // project.pbxproj
@@ -162,6 +645,14 @@ impl XcodeProject {
         })
     }
 
+    /// Resolve the filesystem path of a file reference or group.
+    ///
+    /// Relative to the project root from [`get_project_root`](Self::get_project_root);
+    /// without one, the result is relative to the project directory.
+    pub fn full_path_of(&self, uuid: &str) -> Option<String> {
+        get_real_path(self, self.get_object(uuid)?)
+    }
+
     // ── Object access ──────────────────────────────────────────────────
 
     /// Get a reference to an object by UUID.
@@ -169,9 +660,46 @@ impl XcodeProject {
         self.objects.get(uuid)
     }
 
+    /// A cheap copy for speculative edits. Shares object storage with `self` until
+    /// either side is mutated, at which point that side copies the objects map.
+    pub fn snapshot(&self) -> Self {
+        self.clone()
+    }
+
+    /// Whether `self` and `other` still share the same object storage.
+    pub fn shares_objects_with(&self, other: &XcodeProject) -> bool {
+        Arc::ptr_eq(&self.objects, &other.objects)
+    }
+
+    /// Mutable access to the objects map, copying it first if it's shared with a snapshot.
+    fn storage_mut(&mut self) -> &mut IndexMap<String, PbxObject> {
+        self.storage_and_reference_keys_mut().0
+    }
+
+    /// [`storage_mut`](Self::storage_mut) together with the registered reference keys,
+    /// for passes that rewrite references in every object.
+    fn storage_and_reference_keys_mut(&mut self) -> (&mut IndexMap<String, PbxObject>, &HashMap<String, Vec<String>>) {
+        (Arc::make_mut(&mut self.objects), &self.extra_reference_keys)
+    }
+
     /// Get a mutable reference to an object by UUID.
     pub fn get_object_mut(&mut self, uuid: &str) -> Option<&mut PbxObject> {
-        self.objects.get_mut(uuid)
+        self.storage_mut().get_mut(uuid)
+    }
+
+    /// Check whether an object with the given UUID exists.
+    pub fn contains(&self, uuid: &str) -> bool {
+        self.objects.contains_key(uuid)
+    }
+
+    /// Number of objects in the project.
+    pub fn object_count(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Whether the project has no objects at all.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
     }
 
     /// Get the root PBXProject object.
@@ -181,7 +709,8 @@ impl XcodeProject {
 
     /// Get a mutable reference to the root PBXProject object.
     pub fn root_object_mut(&mut self) -> Option<&mut PbxObject> {
-        self.objects.get_mut(&self.root_object_uuid)
+        let root_uuid = self.root_object_uuid.clone();
+        self.storage_mut().get_mut(&root_uuid)
     }
 
     /// Iterate over all objects.
@@ -191,7 +720,7 @@ impl XcodeProject {
 
     /// Iterate over all objects mutably.
     pub fn objects_mut(&mut self) -> impl Iterator<Item = (&String, &mut PbxObject)> {
-        self.objects.iter_mut()
+        self.storage_mut().iter_mut()
     }
 
     /// Get all objects with a specific ISA type.
@@ -199,14 +728,114 @@ impl XcodeProject {
         self.objects.values().filter(|obj| obj.isa == isa).collect()
     }
 
+    /// Apply `f` to every object with a specific ISA type.
+    pub fn for_each_object_mut<F: FnMut(&mut PbxObject)>(&mut self, isa: &str, mut f: F) {
+        for obj in self.storage_mut().values_mut().filter(|obj| obj.isa == isa) {
+            f(obj);
+        }
+    }
+
+    /// Count objects per ISA type, most common first (ties ordered by ISA name).
+    pub fn isa_histogram(&self) -> IndexMap<String, usize> {
+        let mut counts: IndexMap<String, usize> = IndexMap::new();
+        for obj in self.objects.values() {
+            *counts.entry(obj.isa.clone()).or_insert(0) += 1;
+        }
+        counts.sort_by(|isa_a, count_a, isa_b, count_b| count_b.cmp(count_a).then_with(|| isa_a.cmp(isa_b)));
+        counts
+    }
+
     /// Get all native targets.
     pub fn native_targets(&self) -> Vec<&PbxObject> {
         self.objects_by_isa("PBXNativeTarget")
     }
 
+    /// Iterate over all targets (native, aggregate and legacy).
+    pub fn targets(&self) -> impl Iterator<Item = &PbxObject> {
+        self.objects
+            .values()
+            .filter(|obj| obj.isa().is_some_and(|isa| isa.is_target()))
+    }
+
+    /// Iterate over all group-like objects, including variant, version and synchronized root groups.
+    pub fn groups(&self) -> impl Iterator<Item = &PbxObject> {
+        self.objects
+            .values()
+            .filter(|obj| obj.isa().is_some_and(|isa| isa.is_group()))
+    }
+
+    /// Iterate over all build phases. Objects with an unrecognized ISA are skipped.
+    pub fn build_phases(&self) -> impl Iterator<Item = &PbxObject> {
+        self.objects
+            .values()
+            .filter(|obj| obj.isa().is_some_and(|isa| isa.is_build_phase()))
+    }
+
     /// Find objects that reference a given UUID.
     pub fn get_referrers(&self, uuid: &str) -> Vec<&PbxObject> {
-        self.objects.values().filter(|obj| obj.is_referencing(uuid)).collect()
+        self.objects
+            .values()
+            .filter(|obj| obj.is_referencing_in(&self.reference_keys_for(obj), uuid))
+            .collect()
+    }
+
+    /// Collect every object transitively referenced from `start_uuid`, including itself.
+    ///
+    /// Any reference naming an object is followed, hex or not (e.g. swift-protobuf's
+    /// `______Target_Protobuf`). References to UUIDs missing from the objects map are not
+    /// followed or included.
+    pub fn reachable_from(&self, start_uuid: &str) -> HashSet<String> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start_uuid.to_string()];
+        while let Some(uuid) = stack.pop() {
+            if visited.contains(&uuid) {
+                continue;
+            }
+            if let Some(obj) = self.objects.get(&uuid) {
+                let refs = obj.collect_references_in(&self.reference_keys_for(obj));
+                stack.extend(refs.into_iter().map(|(_, r)| r).filter(|r| !visited.contains(r)));
+                visited.insert(uuid);
+            }
+        }
+        visited
+    }
+
+    /// Treat `keys` as UUID-reference properties of every `isa` object, on top of the
+    /// built-in [`PbxObject::reference_keys`].
+    ///
+    /// Lets newer Xcode properties or custom ISAs take part in reference walking,
+    /// orphan detection and [`remove_object`](Self::remove_object). Registering a key
+    /// twice has no extra effect.
+    ///
+    /// The registry belongs to the project: `XcodeProject` methods such as
+    /// [`get_referrers`](Self::get_referrers), [`collect_references`](Self::collect_references),
+    /// [`reachable_from`](Self::reachable_from), [`replace_reference`](Self::replace_reference)
+    /// and [`find_orphaned_references`](Self::find_orphaned_references) honor it, but the
+    /// reference helpers on a lone [`PbxObject`] only know the built-in keys.
+    pub fn register_reference_keys(&mut self, isa: &str, keys: &[&str]) {
+        let registered = self.extra_reference_keys.entry(isa.to_string()).or_default();
+        for key in keys {
+            if !registered.iter().any(|k| k == key) {
+                registered.push(key.to_string());
+            }
+        }
+    }
+
+    /// Reference-bearing properties of `obj`: the built-in ones followed by any
+    /// registered with [`register_reference_keys`](Self::register_reference_keys).
+    pub fn reference_keys_for<'a>(&'a self, obj: &PbxObject) -> Vec<&'a str> {
+        reference_keys_with(obj, &self.extra_reference_keys)
+    }
+
+    /// Collect all UUIDs an object references, including through registered reference keys.
+    pub fn collect_references(&self, uuid: &str) -> HashSet<String> {
+        let Some(obj) = self.get_object(uuid) else {
+            return HashSet::new();
+        };
+        obj.collect_references_in(&self.reference_keys_for(obj))
+            .into_iter()
+            .map(|(_, r)| r)
+            .collect()
     }
 
     /// Generate a unique UUID for the project.
@@ -221,80 +850,282 @@ impl XcodeProject {
         let uuid = self.get_unique_id(&seed);
         let pairs: PlistObject<'static> = props.into_iter().collect();
         let obj = PbxObject::from_plist(uuid.clone(), &pairs);
-        self.objects.insert(uuid.clone(), obj);
+        self.storage_mut().insert(uuid.clone(), obj);
         uuid
     }
 
     /// Delete an object by UUID.
     pub fn delete_object(&mut self, uuid: &str) -> Option<PbxObject> {
-        self.objects.shift_remove(uuid)
+        self.storage_mut().shift_remove(uuid)
     }
 
     /// Remove an object and all references to it.
     pub fn remove_object(&mut self, uuid: &str) {
         self.delete_object(uuid);
         // Remove references from all other objects
-        let keys: Vec<String> = self.objects.keys().cloned().collect();
-        for key in keys {
-            if let Some(obj) = self.objects.get_mut(&key) {
-                obj.remove_reference(uuid);
+        let (objects, extra) = self.storage_and_reference_keys_mut();
+        for obj in objects.values_mut() {
+            let keys = reference_keys_with(obj, extra);
+            obj.remove_reference_in(&keys, uuid);
+        }
+    }
+
+    /// Rewrite every reference to `old_uuid` across all objects to point at `new_uuid`.
+    ///
+    /// The counterpart of [`remove_object`](Self::remove_object)'s reference cleanup; neither
+    /// object is created or deleted. Returns the number of references rewritten.
+    pub fn replace_reference(&mut self, old_uuid: &str, new_uuid: &str) -> usize {
+        let referrers: Vec<String> = self
+            .get_referrers(old_uuid)
+            .iter()
+            .map(|obj| obj.uuid.clone())
+            .collect();
+        let mut replaced = 0;
+        let (objects, extra) = self.storage_and_reference_keys_mut();
+        for uuid in referrers {
+            if let Some(obj) = objects.get_mut(&uuid) {
+                let keys = reference_keys_with(obj, extra);
+                replaced += obj.replace_reference_in(&keys, old_uuid, new_uuid);
             }
         }
+        if self.root_object_uuid == old_uuid {
+            self.root_object_uuid = new_uuid.to_string();
+            replaced += 1;
+        }
+        replaced
+    }
+
+    /// Change an object's ISA in place, keeping its UUID and properties.
+    ///
+    /// UUID-reference properties the old ISA tracked but the new one doesn't
+    /// (e.g. `children` when turning a group into a file reference) are removed,
+    /// so the object can't end up holding references nothing knows to maintain.
+    /// Returns false if the object doesn't exist or `new_isa` isn't a known ISA.
+    pub fn retag_object(&mut self, uuid: &str, new_isa: &str) -> bool {
+        if new_isa.parse::<Isa>().is_err() {
+            return false;
+        }
+        let obj = match self.storage_mut().get_mut(uuid) {
+            Some(o) => o,
+            None => return false,
+        };
+        let old_keys: Vec<String> = obj.reference_keys().into_iter().map(String::from).collect();
+        obj.isa = new_isa.to_string();
+        obj.props.insert(
+            Cow::Borrowed("isa"),
+            PlistValue::String(Cow::Owned(new_isa.to_string())),
+        );
+        let new_keys = obj.reference_keys();
+        let stale: Vec<String> = old_keys
+            .into_iter()
+            .filter(|k| !new_keys.contains(&k.as_str()))
+            .collect();
+        for key in stale {
+            obj.props.shift_remove(key.as_str());
+        }
+        true
     }
 
     // ── Validation ──────────────────────────────────────────────────────
 
-    /// Find all orphaned references in the project.
+    /// Find reference cycles, such as a group listing one of its ancestors as a child.
     ///
-    /// Returns a list of references where an object points to a UUID
-    /// that doesn't exist in the objects map.
-    pub fn find_orphaned_references(&self) -> Vec<OrphanedReference> {
-        let mut orphans = Vec::new();
+    /// Each cycle is reported as the UUID path from its first object back to the one that
+    /// references it again. `containerPortal` back-pointers to the project aren't followed,
+    /// since every target dependency would otherwise close a loop through them.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let edges = |uuid: &str| -> Vec<&str> {
+            let Some(obj) = self.get_object(uuid) else {
+                return Vec::new();
+            };
+            self.reference_keys_for(obj)
+                .into_iter()
+                .filter(|key| *key != "containerPortal")
+                .flat_map(|key| match obj.props.get(key) {
+                    Some(PlistValue::String(s)) => vec![s.as_ref()],
+                    Some(PlistValue::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+                    _ => Vec::new(),
+                })
+                .filter(|uuid| self.contains(uuid))
+                .collect()
+        };
 
-        for (uuid, obj) in &self.objects {
-            for key in obj.reference_keys() {
-                if let Some(value) = obj.props.get(key) {
-                    match value {
-                        PlistValue::String(ref_uuid) if !ref_uuid.is_empty() => {
-                            if !self.objects.contains_key(&**ref_uuid) {
-                                orphans.push(OrphanedReference {
-                                    referrer_uuid: uuid.clone(),
-                                    referrer_isa: obj.isa.clone(),
-                                    property: key.to_string(),
-                                    orphan_uuid: ref_uuid.to_string(),
-                                });
-                            }
-                        }
-                        PlistValue::Array(items) => {
-                            for item in items {
-                                if let Some(ref_uuid) = item.as_str() {
-                                    if !ref_uuid.is_empty() && !self.objects.contains_key(ref_uuid) {
-                                        orphans.push(OrphanedReference {
-                                            referrer_uuid: uuid.clone(),
-                                            referrer_isa: obj.isa.clone(),
-                                            property: key.to_string(),
-                                            orphan_uuid: ref_uuid.to_string(),
-                                        });
-                                    }
-                                }
-                            }
+        let mut cycles = Vec::new();
+        let mut done: HashSet<&str> = HashSet::new();
+        for start in self.objects.keys() {
+            if done.contains(start.as_str()) {
+                continue;
+            }
+            // Iterative DFS: `path` holds the current chain, `on_path` each member's index in it.
+            let mut path: Vec<&str> = vec![start];
+            let mut on_path: HashMap<&str, usize> = HashMap::from([(start.as_str(), 0)]);
+            let mut stack = vec![edges(start).into_iter()];
+            while let Some(children) = stack.last_mut() {
+                match children.next() {
+                    Some(child) => {
+                        if let Some(&index) = on_path.get(child) {
+                            cycles.push(path[index..].iter().map(|uuid| uuid.to_string()).collect());
+                        } else if !done.contains(child) {
+                            on_path.insert(child, path.len());
+                            path.push(child);
+                            stack.push(edges(child).into_iter());
                         }
-                        _ => {}
+                    }
+                    None => {
+                        stack.pop();
+                        let node = path.pop().unwrap_or_default();
+                        on_path.remove(node);
+                        done.insert(node);
                     }
                 }
             }
         }
+        cycles
+    }
 
+    /// Find all orphaned references in the project.
+    ///
+    /// Returns a list of references where an object points to a UUID
+    /// that doesn't exist in the objects map.
+    pub fn find_orphaned_references(&self) -> Vec<OrphanedReference> {
+        let mut orphans = Vec::new();
+        for obj in self.objects.values() {
+            self.collect_orphans(obj, &mut orphans);
+        }
         orphans
     }
 
-    // ── High-level helpers ─────────────────────────────────────────────
-
-    /// Get the main group UUID from the root object.
-    pub fn main_group_uuid(&self) -> Option<String> {
-        self.root_object()
-            .and_then(|root| root.get_str("mainGroup"))
-            .map(|s| s.to_string())
+    /// Find orphaned references held by the given objects only.
+    ///
+    /// Checks the same reference keys as [`find_orphaned_references`](Self::find_orphaned_references)
+    /// but skips the rest of the project, so validating a localized edit stays cheap.
+    /// UUIDs that aren't in the project are ignored.
+    pub fn find_orphaned_references_in(&self, uuids: &[String]) -> Vec<OrphanedReference> {
+        let mut orphans = Vec::new();
+        for obj in uuids.iter().filter_map(|uuid| self.objects.get(uuid)) {
+            self.collect_orphans(obj, &mut orphans);
+        }
+        orphans
+    }
+
+    fn collect_orphans(&self, obj: &PbxObject, orphans: &mut Vec<OrphanedReference>) {
+        for key in self.reference_keys_for(obj) {
+            let (refs, in_array): (Vec<&str>, bool) = match obj.props.get(key) {
+                Some(PlistValue::String(ref_uuid)) => (vec![ref_uuid], false),
+                Some(PlistValue::Array(items)) => (items.iter().filter_map(|item| item.as_str()).collect(), true),
+                _ => continue,
+            };
+            for ref_uuid in refs {
+                if !ref_uuid.is_empty() && !self.objects.contains_key(ref_uuid) {
+                    orphans.push(OrphanedReference {
+                        referrer_uuid: obj.uuid.clone(),
+                        referrer_isa: obj.isa.clone(),
+                        property: key.to_string(),
+                        orphan_uuid: ref_uuid.to_string(),
+                        in_array,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Flag build settings Xcode would reject, across the project and every target.
+    ///
+    /// Checks for non-numeric `SWIFT_VERSION`s, deployment targets below the oldest
+    /// Xcode supports, keys that differ only in case, and boolean settings set to
+    /// something other than `YES`/`NO`. Values containing `$(` are not evaluated.
+    pub fn lint_build_settings(&self) -> Vec<SettingWarning> {
+        // Keys whose defaults are YES/NO in every variant are booleans.
+        let mut boolean_keys: HashSet<&str> = BOOLEAN_BUILD_SETTINGS.iter().copied().collect();
+        let mut other_keys = HashSet::new();
+        let defaults = ProjectDefaultBuildSettings::for_variant(ConfigVariant::Debug)
+            .into_iter()
+            .chain(ProjectDefaultBuildSettings::for_variant(ConfigVariant::Release));
+        for (key, value) in defaults {
+            if value == "YES" || value == "NO" {
+                boolean_keys.insert(key);
+            } else {
+                other_keys.insert(key);
+            }
+        }
+        boolean_keys.retain(|key| !other_keys.contains(key));
+
+        let mut owners: Vec<(Option<String>, Option<String>)> = vec![(None, self.build_configuration_list_uuid())];
+        for target_uuid in self.target_uuids() {
+            if let Some(target) = self.get_object(&target_uuid) {
+                owners.push((
+                    Some(target.get_str("name").unwrap_or(&target_uuid).to_string()),
+                    target.get_str("buildConfigurationList").map(|s| s.to_string()),
+                ));
+            }
+        }
+
+        let mut warnings = Vec::new();
+        for (target, config_list_uuid) in owners {
+            let config_uuids: Vec<&str> = config_list_uuid
+                .as_deref()
+                .and_then(|uuid| self.get_object(uuid))
+                .and_then(|list| list.get_array("buildConfigurations"))
+                .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+
+            for config in config_uuids.into_iter().filter_map(|uuid| self.get_object(uuid)) {
+                let Some(settings) = config.get_object("buildSettings") else {
+                    continue;
+                };
+                let config_name = config.get_str("name").unwrap_or("").to_string();
+                let mut warn = |key: &str, message: String| {
+                    warnings.push(SettingWarning {
+                        target: target.clone(),
+                        config: config_name.clone(),
+                        key: key.to_string(),
+                        message,
+                    });
+                };
+
+                let mut seen: HashMap<String, &str> = HashMap::new();
+                for (key, value) in settings {
+                    let key = key.as_ref();
+                    if let Some(first) = seen.insert(key.to_ascii_uppercase(), key) {
+                        if first != key {
+                            warn(key, format!("Duplicates {} with different case", first));
+                        }
+                    }
+
+                    let Some(text) = setting_text(value) else {
+                        continue;
+                    };
+                    if text.contains("$(") {
+                        continue;
+                    }
+
+                    if key == "SWIFT_VERSION" && parse_version(&text).is_none() {
+                        warn(key, format!("SWIFT_VERSION must be numeric, got \"{}\"", text));
+                    } else if let Some(floor) = MIN_DEPLOYMENT_TARGETS.get(key) {
+                        match parse_version(&text) {
+                            Some(version) if version < parse_version(floor).unwrap_or_default() => warn(
+                                key,
+                                format!("Deployment target {} is below the minimum of {}", text, floor),
+                            ),
+                            None => warn(key, format!("Deployment target must be a version, got \"{}\"", text)),
+                            _ => {}
+                        }
+                    } else if boolean_keys.contains(key) && text != "YES" && text != "NO" {
+                        warn(key, format!("Expected YES or NO, got \"{}\"", text));
+                    }
+                }
+            }
+        }
+        warnings
+    }
+
+    // ── High-level helpers ─────────────────────────────────────────────
+
+    /// Get the main group UUID from the root object.
+    pub fn main_group_uuid(&self) -> Option<String> {
+        self.root_object()
+            .and_then(|root| root.get_str("mainGroup"))
+            .map(|s| s.to_string())
     }
 
     /// Get the product ref group UUID from the root object.
@@ -311,6 +1142,19 @@ impl XcodeProject {
             .map(|s| s.to_string())
     }
 
+    /// Get the XCConfigurationList UUID of a target (or of the root project, given its UUID).
+    pub fn target_config_list(&self, target_uuid: &str) -> Option<String> {
+        self.get_object(target_uuid)?
+            .get_str("buildConfigurationList")
+            .map(|s| s.to_string())
+    }
+
+    /// Get the `defaultConfigurationName` of a target's configuration list.
+    pub fn default_config_name(&self, target_uuid: &str) -> Option<String> {
+        let config_list = self.get_object(&self.target_config_list(target_uuid)?)?;
+        config_list.get_str("defaultConfigurationName").map(|s| s.to_string())
+    }
+
     /// Get all target UUIDs from the root project.
     pub fn target_uuids(&self) -> Vec<String> {
         self.root_object()
@@ -331,6 +1175,29 @@ impl XcodeProject {
         None
     }
 
+    /// Whether a target builds an application: a regular app, an App Clip
+    /// (`on-demand-install-capable`), a watch app or an iMessage app.
+    pub fn is_app_target(&self, uuid: &str) -> bool {
+        self.target_product_extension(uuid) == Some("app")
+    }
+
+    /// Whether a target builds a unit-test or UI-testing bundle.
+    pub fn is_test_target(&self, uuid: &str) -> bool {
+        self.target_product_extension(uuid) == Some("xctest")
+    }
+
+    /// Whether a target builds an app extension, including WatchKit, iMessage
+    /// and ExtensionKit extensions.
+    pub fn is_extension_target(&self, uuid: &str) -> bool {
+        self.target_product_extension(uuid) == Some("appex")
+    }
+
+    /// The file extension of a target's product, from its `productType`.
+    fn target_product_extension(&self, uuid: &str) -> Option<&'static str> {
+        let product_type = self.get_object(uuid)?.get_str("productType")?;
+        PRODUCT_UTI_EXTENSIONS.get(product_type).copied()
+    }
+
     /// Find the main app target (heuristic based on deployment target).
     pub fn find_main_app_target(&self, platform: &str) -> Option<&PbxObject> {
         let deployment_key = match platform {
@@ -415,23 +1282,42 @@ impl XcodeProject {
             .and_then(|uuid| self.get_object(uuid))
     }
 
+    /// Get the `defaultConfigurationName` of a configuration list.
+    pub fn default_configuration_name(&self, config_list_uuid: &str) -> Option<String> {
+        self.get_object(config_list_uuid)?
+            .get_str("defaultConfigurationName")
+            .map(|s| s.to_string())
+    }
+
+    /// Make `name` the default configuration of a configuration list, e.g. to have
+    /// command-line builds use Debug.
+    /// Returns false if the list has no configuration called `name`.
+    pub fn set_default_configuration(&mut self, config_list_uuid: &str, name: &str) -> bool {
+        if self.find_configuration(config_list_uuid, name).is_none() {
+            return false;
+        }
+        let Some(config_list) = self.get_object_mut(config_list_uuid) else {
+            return false;
+        };
+        config_list.set_sorted(
+            "defaultConfigurationName",
+            PlistValue::String(Cow::Owned(name.to_string())),
+        );
+        true
+    }
+
     /// Get a build setting value from a target's default configuration.
     pub fn get_build_setting(&self, target_uuid: &str, key: &str) -> Option<PlistValue<'static>> {
-        let target = self.get_object(target_uuid)?;
-        let config_list_uuid = target.get_str("buildConfigurationList")?;
-        let config = self.get_default_configuration(config_list_uuid)?;
+        let config_list_uuid = self.target_config_list(target_uuid)?;
+        let config = self.get_default_configuration(&config_list_uuid)?;
         let build_settings = config.get_object("buildSettings")?;
         build_settings.iter().find(|(k, _)| k.as_ref() == key).map(|(_, v)| v.clone())
     }
 
     /// Set a build setting on all configurations for a target.
     pub fn set_build_setting(&mut self, target_uuid: &str, key: &str, value: PlistValue<'static>) -> bool {
-        let target = match self.get_object(target_uuid) {
-            Some(t) => t,
-            None => return false,
-        };
-        let config_list_uuid = match target.get_str("buildConfigurationList") {
-            Some(s) => s.to_string(),
+        let config_list_uuid = match self.target_config_list(target_uuid) {
+            Some(s) => s,
             None => return false,
         };
         let config_list = match self.get_object(&config_list_uuid) {
@@ -457,6 +1343,187 @@ impl XcodeProject {
         true
     }
 
+    /// Get a build setting from the project's default configuration, which every
+    /// target inherits unless it overrides the setting.
+    pub fn get_project_build_setting(&self, key: &str) -> Option<PlistValue<'static>> {
+        self.get_build_setting(&self.root_object_uuid, key)
+    }
+
+    /// Set a build setting on all of the project's configurations.
+    pub fn set_project_build_setting(&mut self, key: &str, value: PlistValue<'static>) -> bool {
+        let root_uuid = self.root_object_uuid.clone();
+        self.set_build_setting(&root_uuid, key, value)
+    }
+
+    /// Get a conditional build setting such as `CODE_SIGN_IDENTITY[sdk=iphoneos*]`
+    /// from a target's default configuration.
+    ///
+    /// `condition` is the text inside the brackets (`sdk=iphoneos*`); an empty condition
+    /// reads the unconditional key. Works for the root project object too, since it
+    /// owns a configuration list like a target.
+    pub fn get_build_setting_conditional(
+        &self,
+        target_uuid: &str,
+        key: &str,
+        condition: &str,
+    ) -> Option<PlistValue<'static>> {
+        self.get_build_setting(target_uuid, &conditional_key(key, condition))
+    }
+
+    /// Set a conditional build setting (`KEY[condition]`) on all configurations for a target.
+    pub fn set_build_setting_conditional(
+        &mut self,
+        target_uuid: &str,
+        key: &str,
+        condition: &str,
+        value: PlistValue<'static>,
+    ) -> bool {
+        self.set_build_setting(target_uuid, &conditional_key(key, condition), value)
+    }
+
+    /// Conditions under which `key` is set in any of a target's configurations,
+    /// in first-seen order. Stacked conditions are returned as written between the
+    /// outer brackets, e.g. `sdk=iphoneos*][arch=arm64`.
+    pub fn list_conditions_for(&self, target_uuid: &str, key: &str) -> Vec<String> {
+        let config_uuids: Vec<&str> = self
+            .get_object(target_uuid)
+            .and_then(|target| target.get_str("buildConfigurationList"))
+            .and_then(|uuid| self.get_object(uuid))
+            .and_then(|list| list.get_array("buildConfigurations"))
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+
+        let mut conditions: Vec<String> = Vec::new();
+        for config in config_uuids.into_iter().filter_map(|uuid| self.get_object(uuid)) {
+            let Some(settings) = config.get_object("buildSettings") else {
+                continue;
+            };
+            for (setting_key, _) in settings {
+                if let (base, Some(condition)) = split_conditional_key(setting_key) {
+                    if base == key && !conditions.iter().any(|c| c == condition) {
+                        conditions.push(condition.to_string());
+                    }
+                }
+            }
+        }
+        conditions
+    }
+
+    /// Copy the `buildSettings` of the configuration named `config_name` from one target
+    /// to the same-named configuration of another, replacing its settings.
+    ///
+    /// The destination configuration is created in the target's configuration list if
+    /// missing. Only settings are copied; `baseConfigurationReference` and other UUIDs are not.
+    /// Returns false if either target has no configuration list or the source lacks the config.
+    pub fn copy_configuration(&mut self, from_target: &str, to_target: &str, config_name: &str) -> bool {
+        let Some(settings) = self
+            .target_config_list(from_target)
+            .and_then(|list| self.find_configuration(&list, config_name))
+            .and_then(|uuid| self.get_object(&uuid))
+            .map(|config| config.get_object("buildSettings").cloned().unwrap_or_default())
+        else {
+            return false;
+        };
+        let Some(dest_list) = self.target_config_list(to_target).filter(|list| self.contains(list)) else {
+            return false;
+        };
+
+        if let Some(dest_uuid) = self.find_configuration(&dest_list, config_name) {
+            return self.set_object_value(&dest_uuid, "buildSettings", PlistValue::Object(settings));
+        }
+
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("XCBuildConfiguration".to_string())),
+        );
+        props.insert(Cow::Owned("buildSettings".to_string()), PlistValue::Object(settings));
+        props.insert(
+            Cow::Owned("name".to_string()),
+            PlistValue::String(Cow::Owned(config_name.to_string())),
+        );
+        let config_uuid = self.create_object(props);
+
+        if let Some(list) = self.get_object_mut(&dest_list) {
+            match list.props.get_mut("buildConfigurations") {
+                Some(PlistValue::Array(ref mut configs)) => configs.push(PlistValue::String(Cow::Owned(config_uuid))),
+                _ => list.set(
+                    "buildConfigurations",
+                    PlistValue::Array(vec![PlistValue::String(Cow::Owned(config_uuid))]),
+                ),
+            }
+        }
+        true
+    }
+
+    /// Build settings of a target's `config_name` configuration, merged over the project's
+    /// same-named configuration and with `$(VAR)` references resolved.
+    ///
+    /// `$(inherited)` expands to the project-level value of the same key, `$(TARGET_NAME)` to
+    /// the target's name, and references to undefined settings to nothing. Array values are
+    /// joined with spaces; conditional keys like `KEY[sdk=iphoneos*]` are left out.
+    pub fn resolved_build_settings(&self, target_uuid: &str, config_name: &str) -> Option<IndexMap<String, String>> {
+        let settings_of = |list_uuid: Option<String>| {
+            list_uuid
+                .and_then(|list| self.find_configuration(&list, config_name))
+                .and_then(|uuid| self.get_object(&uuid))
+                .and_then(|config| config.get_object("buildSettings"))
+                .map(|settings| {
+                    settings
+                        .iter()
+                        .filter(|(key, _)| split_conditional_key(key).1.is_none())
+                        .filter_map(|(key, value)| Some((key.to_string(), setting_words(value)?)))
+                        .collect::<IndexMap<String, String>>()
+                })
+        };
+        let target_settings = settings_of(Some(self.target_config_list(target_uuid)?))?;
+        let project_settings = settings_of(self.build_configuration_list_uuid()).unwrap_or_default();
+
+        let mut merged: IndexMap<String, String> = project_settings
+            .iter()
+            .map(|(key, value)| (key.clone(), expand_inherited(value, "")))
+            .collect();
+        for (key, value) in target_settings {
+            let inherited = merged.get(&key).cloned().unwrap_or_default();
+            merged.insert(key, expand_inherited(&value, &inherited));
+        }
+        let target_name = self.get_target_name(target_uuid).unwrap_or_default();
+
+        let resolved = merged
+            .iter()
+            .map(|(key, value)| {
+                // Every substitution goes through `lookup`, so capping lookups ends self-referencing cycles.
+                let budget = std::cell::Cell::new(MAX_SETTING_LOOKUPS);
+                let lookup = |name: &str| {
+                    if budget.get() == 0 {
+                        return None;
+                    }
+                    budget.set(budget.get() - 1);
+                    match merged.get(name) {
+                        Some(value) => Some(value.clone()),
+                        None if name == "TARGET_NAME" => Some(target_name.clone()),
+                        None => None,
+                    }
+                };
+                (key.clone(), resolve_xcode_build_setting(value, &lookup))
+            })
+            .collect();
+        Some(resolved)
+    }
+
+    /// UUID of the configuration named `name` in a configuration list.
+    fn find_configuration(&self, config_list_uuid: &str, name: &str) -> Option<String> {
+        self.get_object(config_list_uuid)?
+            .get_array("buildConfigurations")?
+            .iter()
+            .filter_map(|v| v.as_str())
+            .find(|uuid| {
+                self.get_object(uuid)
+                    .is_some_and(|config| config.get_str("name") == Some(name))
+            })
+            .map(|uuid| uuid.to_string())
+    }
+
     // ── File & group operations ──────────────────────────────────────
 
     /// Get children UUIDs of a group.
@@ -467,28 +1534,104 @@ impl XcodeProject {
             .unwrap_or_default()
     }
 
+    /// Collect the PBXFileReferences anywhere under a group, in depth-first order.
+    ///
+    /// Descends into nested groups, variant groups and version groups. Each group is
+    /// visited once, so a malformed project whose groups form a cycle still terminates.
+    pub fn iter_group_files(&self, group_uuid: &str) -> Vec<String> {
+        let mut files = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![group_uuid.to_string()];
+        while let Some(uuid) = stack.pop() {
+            if !visited.insert(uuid.clone()) {
+                continue;
+            }
+            let Some(obj) = self.get_object(&uuid) else {
+                continue;
+            };
+            match obj.isa.as_str() {
+                "PBXFileReference" => files.push(uuid),
+                isa if is_children_group(isa) => {
+                    // Push in reverse so children pop in their listed order.
+                    stack.extend(self.get_group_children(&uuid).into_iter().rev());
+                }
+                _ => {}
+            }
+        }
+        files
+    }
+
     /// Add a file reference to the project and a group.
     /// Returns the UUID of the new PBXFileReference.
     pub fn add_file(&mut self, group_uuid: &str, path: &str) -> Option<String> {
-        let ext = std::path::Path::new(path)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-
-        let file_type = crate::types::constants::FILE_TYPES_BY_EXTENSION
-            .get(ext)
-            .copied()
-            .unwrap_or("file");
-
-        let source_tree = crate::types::constants::SOURCETREE_BY_FILETYPE
-            .get(file_type)
-            .copied()
-            .unwrap_or("<group>");
-
-        let name = std::path::Path::new(path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(path);
+        self.add_file_with_options(group_uuid, path, FileOptions::default())
+    }
+
+    /// Add a file reference unless the group already has one for the same path.
+    ///
+    /// A child `PBXFileReference` with the same `path` and `sourceTree` resolves to the
+    /// same location, so its UUID is returned instead of creating a duplicate.
+    pub fn add_file_idempotent(&mut self, group_uuid: &str, path: &str) -> Option<String> {
+        let source_tree = infer_source_tree(infer_file_type(path));
+
+        let existing = self.get_group_children(group_uuid).into_iter().find(|child| {
+            self.get_object(child).is_some_and(|obj| {
+                obj.isa == "PBXFileReference"
+                    && obj.get_str("path") == Some(path)
+                    && obj.get_str("sourceTree").unwrap_or("<group>") == source_tree
+            })
+        });
+        match existing {
+            Some(uuid) => Some(uuid),
+            None => self.add_file(group_uuid, path),
+        }
+    }
+
+    /// Add a file to a group and to the matching build phase of a target in one call.
+    ///
+    /// Sources go to the Sources phase, resources to Resources and frameworks to
+    /// Frameworks; the phase is created if the target doesn't have one yet.
+    /// Returns `(file_ref_uuid, build_file_uuid)`, or `None` without adding anything if
+    /// `target_uuid` isn't a target, `group_uuid` isn't a group, or no phase builds the
+    /// file, as with headers, `.xcconfig`/`.entitlements` files and `Info.plist`; add
+    /// those with [`add_file`](Self::add_file).
+    pub fn add_source_file(&mut self, target_uuid: &str, group_uuid: &str, path: &str) -> Option<(String, String)> {
+        let is_target = self.get_object(target_uuid)?.isa().is_some_and(|isa| isa.is_target());
+        let is_group = self
+            .get_object(group_uuid)
+            .is_some_and(|group| is_children_group(&group.isa));
+        if !is_target || !is_group {
+            return None;
+        }
+        let is_info_plist = Path::new(path).file_name().is_some_and(|name| name == "Info.plist");
+        let phase_isa = match file_category(infer_file_type(path)) {
+            FileCategory::Source => "PBXSourcesBuildPhase",
+            FileCategory::Resource if !is_info_plist => "PBXResourcesBuildPhase",
+            FileCategory::Framework => "PBXFrameworksBuildPhase",
+            _ => return None,
+        };
+        let file_ref_uuid = self.add_file(group_uuid, path)?;
+        let phase_uuid = self.ensure_build_phase(target_uuid, phase_isa)?;
+        let build_file_uuid = self.add_build_file(&phase_uuid, &file_ref_uuid)?;
+        Some((file_ref_uuid, build_file_uuid))
+    }
+
+    /// Add a file reference like [`add_file`](Self::add_file), overriding any of the
+    /// inferred `sourceTree`, `lastKnownFileType` or `name` via `options`.
+    /// Returns the UUID of the new PBXFileReference.
+    pub fn add_file_with_options(&mut self, group_uuid: &str, path: &str, options: FileOptions) -> Option<String> {
+        let file_type = options.file_type.as_deref().unwrap_or_else(|| infer_file_type(path));
+        let source_tree = options
+            .source_tree
+            .as_deref()
+            .unwrap_or_else(|| infer_source_tree(file_type));
+
+        let name = options.name.as_deref().unwrap_or_else(|| {
+            std::path::Path::new(path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(path)
+        });
 
         let mut props = PlistMap::default();
         props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("PBXFileReference".to_string())));
@@ -535,85 +1678,548 @@ impl XcodeProject {
         Some(group_uuid)
     }
 
-    // ── Build phase operations ─────────────────────────────────────
-
-    /// Add a build file to a build phase (e.g. adding a source file to the Sources phase).
-    /// Returns the UUID of the new PBXBuildFile.
-    pub fn add_build_file(&mut self, phase_uuid: &str, file_ref_uuid: &str) -> Option<String> {
-        let mut props = PlistMap::default();
-        props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("PBXBuildFile".to_string())));
-        props.insert(Cow::Owned("fileRef".to_string()), PlistValue::String(Cow::Owned(file_ref_uuid.to_string())));
+    /// Add a folder reference (a "blue folder") to a group.
+    ///
+    /// Unlike [`add_group`](Self::add_group), which creates a logical group, this is a
+    /// single `PBXFileReference` with `lastKnownFileType = folder` whose contents are
+    /// copied as-is; nothing inside the folder is added to the project. When `target_uuid`
+    /// is given, the folder is also added to that target's Resources phase.
+    /// Returns the UUID of the new PBXFileReference.
+    pub fn add_folder_reference(&mut self, group_uuid: &str, path: &str, target_uuid: Option<&str>) -> Option<String> {
+        self.add_resource_reference(group_uuid, path, "folder", target_uuid)
+    }
 
-        let build_file_uuid = self.create_object(props);
+    /// Add an asset catalog (`.xcassets`) to a group and to the target's Resources phase,
+    /// creating the phase if needed. Returns the UUID of the new PBXFileReference.
+    pub fn add_asset_catalog(&mut self, target_uuid: &str, group_uuid: &str, path: &str) -> Option<String> {
+        self.add_resource_reference(group_uuid, path, "folder.assetcatalog", Some(target_uuid))
+    }
 
-        if let Some(phase) = self.get_object_mut(phase_uuid) {
-            if let Some(PlistValue::Array(ref mut files)) = phase.props.get_mut("files") {
-                files.push(PlistValue::String(Cow::Owned(build_file_uuid.clone())));
+    /// Add a directory-like reference of `file_type` to a group and, if given, to the
+    /// target's Resources phase. Nothing is created unless the group exists and
+    /// `target_uuid` is a target.
+    fn add_resource_reference(
+        &mut self,
+        group_uuid: &str,
+        path: &str,
+        file_type: &str,
+        target_uuid: Option<&str>,
+    ) -> Option<String> {
+        self.get_object(group_uuid)?;
+        if let Some(target_uuid) = target_uuid {
+            if !self.get_object(target_uuid)?.isa().is_some_and(|isa| isa.is_target()) {
+                return None;
             }
         }
+        let options = FileOptions {
+            file_type: Some(file_type.to_string()),
+            ..FileOptions::default()
+        };
+        let file_ref_uuid = self.add_file_with_options(group_uuid, path, options)?;
+        if let Some(file_ref) = self.get_object_mut(&file_ref_uuid) {
+            file_ref.remove("fileEncoding");
+        }
 
-        Some(build_file_uuid)
+        if let Some(target_uuid) = target_uuid {
+            let phase_uuid = self.ensure_build_phase(target_uuid, "PBXResourcesBuildPhase")?;
+            self.add_build_file(&phase_uuid, &file_ref_uuid)?;
+        }
+        Some(file_ref_uuid)
     }
 
-    /// Find or create a build phase of a given type for a target.
-    /// Returns the UUID of the build phase.
-    pub fn ensure_build_phase(&mut self, target_uuid: &str, phase_isa: &str) -> Option<String> {
-        // Check if it already exists
-        if let Some(existing) = self.find_build_phase(target_uuid, phase_isa) {
-            return Some(existing.uuid.clone());
+    /// Place `child_uuid` at `index` in a group's `children`, e.g. right after a
+    /// newly created [`add_group`](Self::add_group) or [`add_file`](Self::add_file)
+    /// to match a desired layout.
+    ///
+    /// A child already in the group is moved; indices past the end append.
+    /// Returns false if `group_uuid` isn't a group.
+    pub fn insert_child_at(&mut self, group_uuid: &str, child_uuid: &str, index: usize) -> bool {
+        let Some(group) = self.get_object_mut(group_uuid) else {
+            return false;
+        };
+        if !is_children_group(&group.isa) {
+            return false;
+        }
+        if !matches!(group.props.get("children"), Some(PlistValue::Array(_))) {
+            group.set_sorted("children", PlistValue::Array(Vec::new()));
         }
+        let Some(PlistValue::Array(children)) = group.props.get_mut("children") else {
+            return false;
+        };
+        children.retain(|child| child.as_str() != Some(child_uuid));
+        let index = index.min(children.len());
+        children.insert(index, PlistValue::String(Cow::Owned(child_uuid.to_string())));
+        true
+    }
 
-        // Create new phase
-        let mut props = PlistMap::default();
-        props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned(phase_isa.to_string())));
-        props.insert(Cow::Owned("buildActionMask".to_string()), PlistValue::Integer(2147483647));
-        props.insert(Cow::Owned("files".to_string()), PlistValue::Array(vec![]));
-        props.insert(Cow::Owned("runOnlyForDeploymentPostprocessing".to_string()), PlistValue::Integer(0));
+    /// Move a group from `from_parent`'s children to the end of `to_parent`'s.
+    ///
+    /// Moving a group into itself or one of its descendants would cut it off from
+    /// the main group in a loop, so that is refused. Returns false if `from_parent`
+    /// doesn't list the group, `to_parent` isn't a group, or the move would create a cycle.
+    pub fn move_group(&mut self, group_uuid: &str, from_parent: &str, to_parent: &str) -> bool {
+        let is_group = |obj: &PbxObject| is_children_group(&obj.isa);
+        if !self.get_object(group_uuid).is_some_and(is_group) || !self.get_object(to_parent).is_some_and(is_group) {
+            return false;
+        }
+        if !self.get_group_children(from_parent).iter().any(|c| c == group_uuid) {
+            return false;
+        }
+        if self.descendant_groups(group_uuid).contains(to_parent) {
+            return false;
+        }
 
-        let phase_uuid = self.create_object(props);
+        if let Some(PlistValue::Array(children)) = self
+            .get_object_mut(from_parent)
+            .and_then(|parent| parent.props.get_mut("children"))
+        {
+            children.retain(|child| child.as_str() != Some(group_uuid));
+        }
+        let Some(parent) = self.get_object_mut(to_parent) else {
+            return false;
+        };
+        match parent.props.get_mut("children") {
+            Some(PlistValue::Array(children)) => children.push(PlistValue::String(Cow::Owned(group_uuid.to_string()))),
+            _ => parent.set_sorted(
+                "children",
+                PlistValue::Array(vec![PlistValue::String(Cow::Owned(group_uuid.to_string()))]),
+            ),
+        }
+        true
+    }
 
-        // Add to target's buildPhases
-        if let Some(target) = self.get_object_mut(target_uuid) {
-            if let Some(PlistValue::Array(ref mut phases)) = target.props.get_mut("buildPhases") {
-                phases.push(PlistValue::String(Cow::Owned(phase_uuid.clone())));
+    /// `group_uuid` and every group nested under it. Each group is visited once,
+    /// so a group tree that already loops still terminates.
+    fn descendant_groups(&self, group_uuid: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![group_uuid.to_string()];
+        while let Some(uuid) = stack.pop() {
+            let is_group = self.get_object(&uuid).is_some_and(|obj| is_children_group(&obj.isa));
+            if is_group && seen.insert(uuid.clone()) {
+                stack.extend(self.get_group_children(&uuid));
             }
         }
-
-        Some(phase_uuid)
+        seen
     }
 
-    /// Add a framework to a target (creates file reference + build file + adds to Frameworks phase).
-    /// Returns the UUID of the PBXBuildFile.
-    pub fn add_framework(&mut self, target_uuid: &str, framework_name: &str) -> Option<String> {
-        let name = if framework_name.ends_with(".framework") {
-            framework_name.to_string()
-        } else {
-            format!("{}.framework", framework_name)
+    /// Rename a group, updating `path` too if the group's path was its name.
+    ///
+    /// Children keep their own (group-relative) paths, so their resolved paths follow
+    /// the group's new path. Returns false if `group_uuid` isn't a group.
+    pub fn rename_group(&mut self, group_uuid: &str, new_name: &str) -> bool {
+        let Some(group) = self.get_object_mut(group_uuid) else {
+            return false;
         };
+        if group.isa != "PBXGroup" && group.isa != "PBXVariantGroup" {
+            return false;
+        }
 
-        let path = format!("System/Library/Frameworks/{}", name);
-
-        // Create PBXFileReference for the framework
-        let mut file_props = PlistMap::default();
-        file_props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("PBXFileReference".to_string())));
-        file_props.insert(
-            Cow::Owned("lastKnownFileType".to_string()),
-            PlistValue::String(Cow::Owned("wrapper.framework".to_string())),
-        );
-        file_props.insert(Cow::Owned("name".to_string()), PlistValue::String(Cow::Owned(name.clone())));
-        file_props.insert(Cow::Owned("path".to_string()), PlistValue::String(Cow::Owned(path)));
-        file_props.insert(Cow::Owned("sourceTree".to_string()), PlistValue::String(Cow::Owned("SDKROOT".to_string())));
-
-        let file_ref_uuid = self.create_object(file_props);
+        let name = group.get_str("name").map(|s| s.to_string());
+        let path = group.get_str("path").map(|s| s.to_string());
+        match (name, path) {
+            (Some(name), Some(path)) if name == path => {
+                group.set_str("name", new_name);
+                group.set_str("path", new_name);
+            }
+            (None, Some(_)) => group.set_str("path", new_name),
+            _ => group.set_str("name", new_name),
+        }
+        true
+    }
 
-        // Ensure Frameworks build phase exists
-        let phase_uuid = self.ensure_build_phase(target_uuid, "PBXFrameworksBuildPhase")?;
+    /// Point a file reference at a renamed file on disk.
+    ///
+    /// Sets `path` to `new_path`, re-infers the file type from the new extension, and
+    /// updates `name` to the new basename if the reference had one. Build files and
+    /// group membership are untouched. Returns false if `file_ref_uuid` isn't a
+    /// `PBXFileReference`.
+    pub fn rename_file(&mut self, file_ref_uuid: &str, new_path: &str) -> bool {
+        let Some(file_ref) = self
+            .get_object_mut(file_ref_uuid)
+            .filter(|obj| obj.isa == "PBXFileReference")
+        else {
+            return false;
+        };
+        file_ref.set_str("path", new_path);
+        let file_type = infer_file_type(new_path);
+        if file_ref.get_str("explicitFileType").is_some() {
+            file_ref.set_str("explicitFileType", file_type);
+        } else {
+            file_ref.set_sorted(
+                "lastKnownFileType",
+                PlistValue::String(Cow::Owned(file_type.to_string())),
+            );
+        }
+        if file_ref.get_str("name").is_some() {
+            let basename = std::path::Path::new(new_path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(new_path);
+            file_ref.set_str("name", basename);
+        }
+        true
+    }
 
-        // Add build file
-        self.add_build_file(&phase_uuid, &file_ref_uuid)
+    /// Set the `sourceTree` of a file reference or group, rejecting values Xcode
+    /// wouldn't understand, e.g. `group` where `<group>` was meant.
+    ///
+    /// Accepts the values in [`SOURCE_TREES`] and build setting names such as
+    /// `PODS_ROOT` (uppercase letters, digits and underscores).
+    pub fn set_source_tree(&mut self, uuid: &str, source_tree: &str) -> Result<(), String> {
+        let is_setting_name = source_tree.starts_with(|c: char| c.is_ascii_uppercase() || c == '_')
+            && source_tree
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+        if !SOURCE_TREES.contains(&source_tree) && !is_setting_name {
+            return Err(format!(
+                "Unknown sourceTree \"{}\": expected one of {} or a build setting name",
+                source_tree,
+                SOURCE_TREES.join(", ")
+            ));
+        }
+        let obj = self
+            .get_object_mut(uuid)
+            .ok_or_else(|| format!("Object {} not found", uuid))?;
+        let has_source_tree = is_children_group(&obj.isa)
+            || matches!(
+                obj.isa.as_str(),
+                "PBXFileReference" | "PBXFileSystemSynchronizedRootGroup" | "PBXReferenceProxy"
+            );
+        if !has_source_tree {
+            return Err(format!("{} {} has no sourceTree", obj.isa, uuid));
+        }
+        obj.set_sorted("sourceTree", PlistValue::String(Cow::Owned(source_tree.to_string())));
+        Ok(())
     }
 
-    // ── Target operations ──────────────────────────────────────────
+    /// Rewrite Windows-style `\` separators in every object's `path` to `/`, which is
+    /// all Xcode accepts. A `name` that mirrored the old path is rewritten with it.
+    ///
+    /// A backslash escaping a space, quote, `$` or another backslash is left alone,
+    /// as are trailing backslashes. Returns the number of objects changed.
+    pub fn normalize_paths(&mut self) -> usize {
+        let mut changed = 0;
+        for obj in self.storage_mut().values_mut() {
+            let Some(normalized) = obj.get_str("path").and_then(normalize_separators) else {
+                continue;
+            };
+            if obj.get_str("name") == obj.get_str("path") {
+                obj.set_str("name", &normalized);
+            }
+            obj.set_str("path", &normalized);
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Merge `PBXFileReference`s that resolve to the same full path.
+    ///
+    /// The first reference for each path is kept; build files and group children
+    /// pointing at a duplicate are repointed to it, and the duplicates are deleted.
+    /// An array entry is dropped only if repointing it would repeat the kept reference;
+    /// other repeated entries are left alone. Returns the number of references removed.
+    pub fn deduplicate_file_references(&mut self) -> usize {
+        let mut keepers: HashMap<String, String> = HashMap::new();
+        let mut replacements: HashMap<String, String> = HashMap::new();
+        for obj in self.objects.values().filter(|o| o.isa == "PBXFileReference") {
+            if let Some(full_path) = get_full_path(self, obj) {
+                match keepers.get(&full_path) {
+                    Some(keeper) => {
+                        replacements.insert(obj.uuid.clone(), keeper.clone());
+                    }
+                    None => {
+                        keepers.insert(full_path, obj.uuid.clone());
+                    }
+                }
+            }
+        }
+        if replacements.is_empty() {
+            return 0;
+        }
+
+        let (objects, extra) = self.storage_and_reference_keys_mut();
+        for obj in objects.values_mut() {
+            for key in reference_keys_with(obj, extra) {
+                match obj.props.get_mut(key) {
+                    Some(PlistValue::String(s)) => {
+                        if let Some(keeper) = replacements.get(&**s) {
+                            *s = Cow::Owned(keeper.clone());
+                        }
+                    }
+                    Some(PlistValue::Array(items)) => {
+                        let mut present: HashSet<String> = items
+                            .iter()
+                            .filter_map(|item| item.as_str())
+                            .map(String::from)
+                            .collect();
+                        items.retain_mut(|item| {
+                            let PlistValue::String(s) = item else {
+                                return true;
+                            };
+                            let Some(keeper) = replacements.get(&**s) else {
+                                return true;
+                            };
+                            if !present.insert(keeper.clone()) {
+                                return false;
+                            }
+                            *s = Cow::Owned(keeper.clone());
+                            true
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        for uuid in replacements.keys() {
+            self.storage_mut().shift_remove(uuid);
+        }
+        replacements.len()
+    }
+
+    // ── Build phase operations ─────────────────────────────────────
+
+    /// Add a build file to a build phase (e.g. adding a source file to the Sources phase).
+    /// Returns the UUID of the new PBXBuildFile.
+    pub fn add_build_file(&mut self, phase_uuid: &str, file_ref_uuid: &str) -> Option<String> {
+        let mut props = PlistMap::default();
+        props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("PBXBuildFile".to_string())));
+        props.insert(Cow::Owned("fileRef".to_string()), PlistValue::String(Cow::Owned(file_ref_uuid.to_string())));
+
+        let build_file_uuid = self.create_object(props);
+
+        if let Some(phase) = self.get_object_mut(phase_uuid) {
+            if let Some(PlistValue::Array(ref mut files)) = phase.props.get_mut("files") {
+                files.push(PlistValue::String(Cow::Owned(build_file_uuid.clone())));
+            }
+        }
+
+        Some(build_file_uuid)
+    }
+
+    /// Remove every PBXBuildFile from a build phase, deleting the build files and emptying `files`.
+    ///
+    /// The file references the build files pointed at are left in place.
+    /// Returns the number of build files deleted.
+    pub fn clear_build_phase(&mut self, phase_uuid: &str) -> usize {
+        let files = match self.get_object_mut(phase_uuid) {
+            Some(phase) if phase.isa.ends_with("BuildPhase") => match phase.props.get_mut("files") {
+                Some(PlistValue::Array(files)) => std::mem::take(files),
+                _ => return 0,
+            },
+            _ => return 0,
+        };
+
+        let mut removed = 0;
+        for uuid in files.iter().filter_map(|v| v.as_str()) {
+            if self.get_object(uuid).is_some_and(|obj| obj.isa == "PBXBuildFile") {
+                self.delete_object(uuid);
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Set a build phase's `runOnlyForDeploymentPostprocessing` and/or `buildActionMask`;
+    /// `None` leaves a flag as is. Install-only script phases, for example, run only
+    /// during deployment postprocessing.
+    /// Returns false if `phase_uuid` isn't a build phase.
+    pub fn set_phase_flags(
+        &mut self,
+        phase_uuid: &str,
+        run_only_postprocessing: Option<bool>,
+        build_action_mask: Option<i64>,
+    ) -> bool {
+        let Some(phase) = self
+            .get_object_mut(phase_uuid)
+            .filter(|p| p.isa.ends_with("BuildPhase"))
+        else {
+            return false;
+        };
+        if let Some(run_only) = run_only_postprocessing {
+            phase.set_sorted(
+                "runOnlyForDeploymentPostprocessing",
+                PlistValue::Integer(run_only as i64),
+            );
+        }
+        if let Some(mask) = build_action_mask {
+            phase.set_sorted("buildActionMask", PlistValue::Integer(mask));
+        }
+        true
+    }
+
+    /// Find or create a build phase of a given type for a target.
+    /// Returns the UUID of the build phase.
+    pub fn ensure_build_phase(&mut self, target_uuid: &str, phase_isa: &str) -> Option<String> {
+        // Check if it already exists
+        if let Some(existing) = self.find_build_phase(target_uuid, phase_isa) {
+            return Some(existing.uuid.clone());
+        }
+
+        Some(self.create_build_phase(target_uuid, phase_isa, None))
+    }
+
+    /// Find or create a build phase matching both ISA and `name` for a target.
+    ///
+    /// Unlike [`ensure_build_phase`](Self::ensure_build_phase), this tells apart the several
+    /// PBXCopyFilesBuildPhases a target may have. A newly created copy-files phase has no
+    /// `dstSubfolderSpec`/`dstPath`; set them on the returned UUID.
+    /// Returns the UUID of the build phase.
+    pub fn ensure_named_build_phase(&mut self, target_uuid: &str, phase_isa: &str, name: &str) -> Option<String> {
+        let target = self.get_object(target_uuid)?;
+        let existing = target.get_array("buildPhases").and_then(|phases| {
+            phases.iter().filter_map(|v| v.as_str()).find(|uuid| {
+                self.get_object(uuid)
+                    .is_some_and(|phase| phase.isa == phase_isa && phase.get_str("name") == Some(name))
+            })
+        });
+        if let Some(uuid) = existing {
+            return Some(uuid.to_string());
+        }
+
+        Some(self.create_build_phase(target_uuid, phase_isa, Some(name)))
+    }
+
+    /// Create an empty build phase and append it to the target's `buildPhases`.
+    fn create_build_phase(&mut self, target_uuid: &str, phase_isa: &str, name: Option<&str>) -> String {
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned(phase_isa.to_string())),
+        );
+        props.insert(
+            Cow::Owned("buildActionMask".to_string()),
+            PlistValue::Integer(2147483647),
+        );
+        props.insert(Cow::Owned("files".to_string()), PlistValue::Array(vec![]));
+        if let Some(name) = name {
+            props.insert(
+                Cow::Owned("name".to_string()),
+                PlistValue::String(Cow::Owned(name.to_string())),
+            );
+        }
+        props.insert(
+            Cow::Owned("runOnlyForDeploymentPostprocessing".to_string()),
+            PlistValue::Integer(0),
+        );
+
+        let phase_uuid = self.create_object(props);
+
+        // Add to target's buildPhases
+        if let Some(target) = self.get_object_mut(target_uuid) {
+            if let Some(PlistValue::Array(ref mut phases)) = target.props.get_mut("buildPhases") {
+                phases.push(PlistValue::String(Cow::Owned(phase_uuid.clone())));
+            }
+        }
+
+        phase_uuid
+    }
+
+    /// Add a framework to a target (creates file reference + build file + adds to Frameworks phase).
+    /// Returns the UUID of the PBXBuildFile.
+    pub fn add_framework(&mut self, target_uuid: &str, framework_name: &str) -> Option<String> {
+        let name = if framework_name.ends_with(".framework") {
+            framework_name.to_string()
+        } else {
+            format!("{}.framework", framework_name)
+        };
+
+        let path = format!("System/Library/Frameworks/{}", name);
+
+        // Create PBXFileReference for the framework
+        let mut file_props = PlistMap::default();
+        file_props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("PBXFileReference".to_string())));
+        file_props.insert(
+            Cow::Owned("lastKnownFileType".to_string()),
+            PlistValue::String(Cow::Owned("wrapper.framework".to_string())),
+        );
+        file_props.insert(Cow::Owned("name".to_string()), PlistValue::String(Cow::Owned(name.clone())));
+        file_props.insert(Cow::Owned("path".to_string()), PlistValue::String(Cow::Owned(path)));
+        file_props.insert(Cow::Owned("sourceTree".to_string()), PlistValue::String(Cow::Owned("SDKROOT".to_string())));
+
+        let file_ref_uuid = self.create_object(file_props);
+
+        // Ensure Frameworks build phase exists
+        let phase_uuid = self.ensure_build_phase(target_uuid, "PBXFrameworksBuildPhase")?;
+
+        // Add build file
+        self.add_build_file(&phase_uuid, &file_ref_uuid)
+    }
+
+    /// Add a custom build rule to a target that runs a script for matching files
+    /// (e.g. compiling `*.proto` files with protoc).
+    ///
+    /// `file_type` is either a UTI (`sourcecode.c.c`) or a glob pattern (`*.proto`);
+    /// patterns are stored as `filePatterns` with `fileType = pattern.proxy`.
+    /// Returns the UUID of the new PBXBuildRule.
+    pub fn add_build_rule(
+        &mut self,
+        target_uuid: &str,
+        file_type: &str,
+        script: &str,
+        output_files: Vec<String>,
+    ) -> Option<String> {
+        self.get_object(target_uuid)?;
+
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXBuildRule".to_string())),
+        );
+        props.insert(
+            Cow::Owned("compilerSpec".to_string()),
+            PlistValue::String(Cow::Owned("com.apple.compilers.proxy.script".to_string())),
+        );
+        if file_type.contains('*') {
+            props.insert(
+                Cow::Owned("filePatterns".to_string()),
+                PlistValue::String(Cow::Owned(file_type.to_string())),
+            );
+            props.insert(
+                Cow::Owned("fileType".to_string()),
+                PlistValue::String(Cow::Owned("pattern.proxy".to_string())),
+            );
+        } else {
+            props.insert(
+                Cow::Owned("fileType".to_string()),
+                PlistValue::String(Cow::Owned(file_type.to_string())),
+            );
+        }
+        props.insert(Cow::Owned("isEditable".to_string()), PlistValue::Integer(1));
+        props.insert(
+            Cow::Owned("outputFiles".to_string()),
+            PlistValue::Array(
+                output_files
+                    .into_iter()
+                    .map(|f| PlistValue::String(Cow::Owned(f)))
+                    .collect(),
+            ),
+        );
+        props.insert(
+            Cow::Owned("script".to_string()),
+            PlistValue::String(Cow::Owned(script.to_string())),
+        );
+
+        let rule_uuid = self.create_object(props);
+
+        if let Some(target) = self.get_object_mut(target_uuid) {
+            match target.props.get_mut("buildRules") {
+                Some(PlistValue::Array(ref mut rules)) => {
+                    rules.push(PlistValue::String(Cow::Owned(rule_uuid.clone())));
+                }
+                _ => {
+                    target.props.insert(
+                        Cow::Owned("buildRules".to_string()),
+                        PlistValue::Array(vec![PlistValue::String(Cow::Owned(rule_uuid.clone()))]),
+                    );
+                }
+            }
+        }
+
+        Some(rule_uuid)
+    }
+
+    // ── Target operations ──────────────────────────────────────────
 
     /// Add a dependency from one target to another.
     /// Returns the UUID of the PBXTargetDependency.
@@ -662,23 +2268,186 @@ impl XcodeProject {
         Some(dep_uuid)
     }
 
+    /// The `PBXTargetDependency` through which `target_uuid` depends on `depends_on_uuid`, if any.
+    fn find_dependency(&self, target_uuid: &str, depends_on_uuid: &str) -> Option<String> {
+        self.get_object(target_uuid)?
+            .get_array("dependencies")?
+            .iter()
+            .filter_map(|dep| dep.as_str())
+            .find(|dep| {
+                self.get_object(dep).is_some_and(|obj| {
+                    obj.isa == "PBXTargetDependency" && obj.get_str("target") == Some(depends_on_uuid)
+                })
+            })
+            .map(String::from)
+    }
+
+    /// UUIDs of the targets a target depends on, in `dependencies` order.
+    ///
+    /// A `PBXTargetDependency` without a `target` is resolved through its proxy's
+    /// `remoteGlobalIDString`; dependencies on targets in other projects are skipped.
+    pub fn get_dependencies(&self, target_uuid: &str) -> Vec<String> {
+        let Some(deps) = self.get_object(target_uuid).and_then(|t| t.get_array("dependencies")) else {
+            return vec![];
+        };
+        deps.iter()
+            .filter_map(|dep| self.get_object(dep.as_str()?))
+            .filter(|dep| dep.isa == "PBXTargetDependency")
+            .filter_map(|dep| {
+                dep.get_str("target").or_else(|| {
+                    self.get_object(dep.get_str("targetProxy")?)?
+                        .get_str("remoteGlobalIDString")
+                })
+            })
+            .filter(|uuid| self.objects.contains_key(*uuid))
+            .map(String::from)
+            .collect()
+    }
+
+    /// UUIDs of the targets that directly depend on `target_uuid`, in project order.
+    ///
+    /// An empty result means no target in this project needs it to be built first.
+    pub fn dependents_of(&self, target_uuid: &str) -> Vec<String> {
+        self.target_uuids()
+            .into_iter()
+            .filter(|uuid| self.get_dependencies(uuid).iter().any(|dep| dep == target_uuid))
+            .collect()
+    }
+
+    /// All targets ordered so that each comes after the targets it depends on.
+    ///
+    /// Independent targets keep their project order. If the dependencies form a cycle,
+    /// returns `Err` with the UUIDs along it, starting from the first target revisited.
+    pub fn target_build_order(&self) -> Result<Vec<String>, Vec<String>> {
+        let mut order = Vec::new();
+        let mut done: HashSet<String> = HashSet::new();
+        for start in self.target_uuids() {
+            if done.contains(&start) {
+                continue;
+            }
+            // Iterative post-order DFS, as in `find_cycles`.
+            let mut path = vec![start.clone()];
+            let mut stack = vec![self.get_dependencies(&start).into_iter()];
+            while let Some(deps) = stack.last_mut() {
+                match deps.next() {
+                    Some(dep) => {
+                        if let Some(index) = path.iter().position(|uuid| *uuid == dep) {
+                            return Err(path[index..].to_vec());
+                        }
+                        if !done.contains(&dep) {
+                            stack.push(self.get_dependencies(&dep).into_iter());
+                            path.push(dep);
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        let node = path.pop().unwrap_or_default();
+                        done.insert(node.clone());
+                        order.push(node);
+                    }
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /// Remove every target not listed in `keep`, then delete objects no longer reachable
+    /// from the project, e.g. to produce a project with just the app target for CI.
+    ///
+    /// Along with the targets, this removes kept targets' dependencies on them, build files
+    /// copying their products, their products, `TargetAttributes` entries and membership
+    /// exception sets. Groups and files stay as long as they're still reachable from the
+    /// main group. Returns the number of targets removed.
+    pub fn retain_targets(&mut self, keep: &[String]) -> usize {
+        let removed: HashSet<String> = self
+            .target_uuids()
+            .into_iter()
+            .filter(|uuid| !keep.contains(uuid))
+            .collect();
+        if removed.is_empty() {
+            return 0;
+        }
+        let products: HashSet<String> = removed
+            .iter()
+            .filter_map(|uuid| self.get_object(uuid)?.get_str("productReference").map(String::from))
+            .collect();
+
+        let dangling: Vec<String> = self
+            .objects
+            .values()
+            .filter(|obj| match obj.isa.as_str() {
+                "PBXTargetDependency" => obj
+                    .get_str("target")
+                    .or_else(|| {
+                        self.get_object(obj.get_str("targetProxy")?)?
+                            .get_str("remoteGlobalIDString")
+                    })
+                    .is_some_and(|target| removed.contains(target)),
+                "PBXBuildFile" => obj
+                    .get_str("fileRef")
+                    .is_some_and(|file_ref| products.contains(file_ref)),
+                "PBXFileSystemSynchronizedBuildFileExceptionSet" => {
+                    obj.get_str("target").is_some_and(|target| removed.contains(target))
+                }
+                _ => false,
+            })
+            .map(|obj| obj.uuid.clone())
+            .collect();
+        for uuid in dangling.iter().chain(&products).chain(&removed) {
+            self.remove_object(uuid);
+        }
+
+        if let Some(PlistValue::Object(attributes)) =
+            self.root_object_mut().and_then(|root| root.props.get_mut("attributes"))
+        {
+            if let Some((_, PlistValue::Object(target_attributes))) =
+                attributes.iter_mut().find(|(k, _)| k.as_ref() == "TargetAttributes")
+            {
+                target_attributes.retain(|(uuid, _)| !removed.contains(uuid.as_ref()));
+            }
+        }
+
+        self.prune_unreachable();
+        removed.len()
+    }
+
+    /// Delete objects that can't be reached from the root object, keeping subprojects'
+    /// product groups, which are referenced only from `projectReferences`.
+    fn prune_unreachable(&mut self) -> usize {
+        let mut seeds = vec![self.root_object_uuid.clone()];
+        if let Some(references) = self.root_object().and_then(|root| root.get_array("projectReferences")) {
+            for reference in references.iter().filter_map(|r| r.as_object()) {
+                seeds.extend(reference.iter().filter_map(|(_, v)| v.as_str().map(String::from)));
+            }
+        }
+        let mut reachable = HashSet::new();
+        for seed in seeds {
+            reachable.extend(self.reachable_from(&seed));
+        }
+        let before = self.objects.len();
+        self.storage_mut().retain(|uuid, _| reachable.contains(uuid));
+        before - self.objects.len()
+    }
+
     /// Create a native target with build configurations and standard build phases.
     /// Returns the UUID of the new PBXNativeTarget.
     ///
     /// This creates:
-    /// - XCBuildConfiguration for Debug and Release
+    /// - XCBuildConfiguration for Debug and Release, seeded with [`ProjectDefaultBuildSettings`]
     /// - XCConfigurationList referencing those configurations
     /// - PBXSourcesBuildPhase, PBXFrameworksBuildPhase, PBXResourcesBuildPhase
     /// - PBXNativeTarget with all of the above
     /// - PBXFileReference for the product (e.g. MyApp.app)
     /// - Adds the product ref to the Products group
     /// - Adds the target to PBXProject.targets
+    ///
+    /// Returns `None` without touching the project if `product_type` is not in
+    /// `PRODUCT_UTI_EXTENSIONS`, rather than guessing the product's extension.
     pub fn create_native_target(&mut self, name: &str, product_type: &str, bundle_id: &str) -> Option<String> {
         // Determine product extension from product type
-        let product_ext = crate::types::constants::PRODUCT_UTI_EXTENSIONS
-            .get(product_type)
-            .copied()
-            .unwrap_or("app");
+        let product_ext = *PRODUCT_UTI_EXTENSIONS.get(product_type)?;
+
+        self.sync_object_version_keys(false);
 
         let product_name = if product_ext.is_empty() {
             name.to_string()
@@ -748,6 +2517,19 @@ impl XcodeProject {
         release_props.insert(Cow::Owned("name".to_string()), PlistValue::String(Cow::Owned("Release".to_string())));
         let release_uuid = self.create_object(release_props);
 
+        for (config_uuid, variant) in [
+            (&debug_uuid, ConfigVariant::Debug),
+            (&release_uuid, ConfigVariant::Release),
+        ] {
+            self.apply_default_settings(config_uuid, variant);
+            if let Some(PlistValue::Object(settings)) = self
+                .get_object_mut(config_uuid)
+                .and_then(|c| c.props.get_mut("buildSettings"))
+            {
+                settings.sort_by(|(a, _), (b, _)| a.cmp(b));
+            }
+        }
+
         // 4. Create XCConfigurationList
         let mut config_list_props = PlistMap::default();
         config_list_props.insert(Cow::Owned("isa".to_string()), PlistValue::String(Cow::Owned("XCConfigurationList".to_string())));
@@ -831,6 +2613,80 @@ impl XcodeProject {
         Some(target_uuid)
     }
 
+    /// Create an external build system target (PBXLegacyTarget) that runs `build_tool_path`,
+    /// e.g. `/usr/bin/make`, with `build_args`. Returns the UUID of the new target.
+    ///
+    /// Creates Debug and Release configurations and adds the target to PBXProject.targets.
+    /// A `None` working directory leaves the tool running in the project directory.
+    pub fn create_legacy_target(
+        &mut self,
+        name: &str,
+        build_tool_path: &str,
+        build_args: &str,
+        build_working_dir: Option<&str>,
+    ) -> Option<String> {
+        self.root_object()?;
+        let string = |s: &str| PlistValue::String(Cow::Owned(s.to_string()));
+
+        // 1. Create Debug and Release build configurations
+        let mut config_uuids = Vec::new();
+        for config_name in ["Debug", "Release"] {
+            let settings: PlistObject<'static> =
+                vec![(Cow::Owned("PRODUCT_NAME".to_string()), string("$(TARGET_NAME)"))];
+            let mut props = PlistMap::default();
+            props.insert(Cow::Owned("isa".to_string()), string("XCBuildConfiguration"));
+            props.insert(Cow::Owned("buildSettings".to_string()), PlistValue::Object(settings));
+            props.insert(Cow::Owned("name".to_string()), string(config_name));
+            config_uuids.push(PlistValue::String(Cow::Owned(self.create_object(props))));
+        }
+
+        // 2. Create XCConfigurationList
+        let mut config_list_props = PlistMap::default();
+        config_list_props.insert(Cow::Owned("isa".to_string()), string("XCConfigurationList"));
+        config_list_props.insert(
+            Cow::Owned("buildConfigurations".to_string()),
+            PlistValue::Array(config_uuids),
+        );
+        config_list_props.insert(
+            Cow::Owned("defaultConfigurationIsVisible".to_string()),
+            PlistValue::Integer(0),
+        );
+        config_list_props.insert(Cow::Owned("defaultConfigurationName".to_string()), string("Release"));
+        let config_list_uuid = self.create_object(config_list_props);
+
+        // 3. Create PBXLegacyTarget
+        let mut target_props = PlistMap::default();
+        target_props.insert(Cow::Owned("isa".to_string()), string("PBXLegacyTarget"));
+        target_props.insert(Cow::Owned("buildArgumentsString".to_string()), string(build_args));
+        target_props.insert(
+            Cow::Owned("buildConfigurationList".to_string()),
+            PlistValue::String(Cow::Owned(config_list_uuid)),
+        );
+        target_props.insert(Cow::Owned("buildPhases".to_string()), PlistValue::Array(vec![]));
+        target_props.insert(Cow::Owned("buildToolPath".to_string()), string(build_tool_path));
+        if let Some(dir) = build_working_dir {
+            target_props.insert(Cow::Owned("buildWorkingDirectory".to_string()), string(dir));
+        }
+        target_props.insert(Cow::Owned("dependencies".to_string()), PlistValue::Array(vec![]));
+        target_props.insert(Cow::Owned("name".to_string()), string(name));
+        target_props.insert(
+            Cow::Owned("passBuildSettingsInEnvironment".to_string()),
+            PlistValue::Integer(1),
+        );
+        target_props.insert(Cow::Owned("productName".to_string()), string(name));
+        let target_uuid = self.create_object(target_props);
+
+        // 4. Add target to PBXProject.targets
+        let root_uuid = self.root_object_uuid.clone();
+        if let Some(root) = self.get_object_mut(&root_uuid) {
+            if let Some(PlistValue::Array(ref mut targets)) = root.props.get_mut("targets") {
+                targets.push(PlistValue::String(Cow::Owned(target_uuid.clone())));
+            }
+        }
+
+        Some(target_uuid)
+    }
+
     // ── Generic object property access ───────────────────────────────
 
     /// Get a string property from any object by UUID and key.
@@ -848,6 +2704,16 @@ impl XcodeProject {
         }
     }
 
+    /// Set a property of any type on any object by UUID and key.
+    pub fn set_object_value(&mut self, uuid: &str, key: &str, value: PlistValue<'static>) -> bool {
+        if let Some(obj) = self.get_object_mut(uuid) {
+            obj.props.insert(Cow::Owned(key.to_string()), value);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Find all object UUIDs matching a given ISA type.
     pub fn find_objects_by_isa(&self, isa: &str) -> Vec<String> {
         self.objects
@@ -857,6 +2723,14 @@ impl XcodeProject {
             .collect()
     }
 
+    /// The human-readable label the writer emits as the inline comment after `uuid`,
+    /// e.g. `AppDelegate.swift in Sources` for a build file.
+    ///
+    /// Returns `None` if the object doesn't exist or is written without a comment.
+    pub fn comment_for(&self, uuid: &str) -> Option<String> {
+        comments::comment_in(uuid, self.objects.iter().map(|(k, v)| (k.as_str(), v)))
+    }
+
     // ── Target name access ─────────────────────────────────────────
 
     /// Get the name of a target.
@@ -871,7 +2745,28 @@ impl XcodeProject {
             .map(|s| s.to_string())
     }
 
-    /// Set the name and productName of a target.
+    /// Path of a target's built product, e.g. `MyApp.app`, read from the file
+    /// reference its `productReference` points at.
+    pub fn product_path(&self, target_uuid: &str) -> Option<String> {
+        let product_ref = self.get_object(target_uuid)?.get_str("productReference")?;
+        self.get_object(product_ref)?.get_str("path").map(|s| s.to_string())
+    }
+
+    /// A target's `PRODUCT_NAME` in its default configuration, with `$(TARGET_NAME)`
+    /// and other references resolved. Falls back to the target's `productName`.
+    pub fn product_name(&self, target_uuid: &str) -> Option<String> {
+        let target = self.get_object(target_uuid)?;
+        let resolved = self
+            .target_config_list(target_uuid)
+            .and_then(|list| self.get_default_configuration(&list))
+            .and_then(|config| config.get_str("name"))
+            .and_then(|config_name| self.resolved_build_settings(target_uuid, config_name))
+            .and_then(|settings| settings.get("PRODUCT_NAME").cloned())
+            .filter(|name| !name.is_empty());
+        resolved.or_else(|| target.get_str("productName").map(|s| s.to_string()))
+    }
+
+    /// Set the name and productName of a target.
     pub fn set_target_name(&mut self, target_uuid: &str, name: &str) -> bool {
         if let Some(target) = self.get_object_mut(target_uuid) {
             target.set_str("name", name);
@@ -959,25 +2854,40 @@ impl XcodeProject {
     /// Returns UUIDs of targets whose products are embedded in the given target
     /// via PBXCopyFilesBuildPhase (e.g. "Embed Foundation Extensions", "Embed Frameworks").
     ///
+    /// See [`embedded_extensions`](Self::embedded_extensions) for the phases involved.
+    pub fn get_embedded_targets(&self, target_uuid: &str) -> Vec<String> {
+        let embedded: HashSet<String> = self
+            .embedded_extensions(target_uuid)
+            .into_iter()
+            .map(|e| e.target_uuid)
+            .collect();
+        self.native_targets()
+            .into_iter()
+            .filter(|t| embedded.contains(&t.uuid))
+            .map(|t| t.uuid.clone())
+            .collect()
+    }
+
+    /// Targets whose products are embedded in the given target, with the
+    /// PBXCopyFilesBuildPhase that copies each one.
+    ///
     /// Walks: target.buildPhases -> PBXCopyFilesBuildPhase -> files -> PBXBuildFile.fileRef
     ///        -> matches against all targets' productReference to resolve target UUIDs.
-    pub fn get_embedded_targets(&self, target_uuid: &str) -> Vec<String> {
-        let target = match self.get_object(target_uuid) {
-            Some(t) => t,
-            None => return vec![],
-        };
-        let phases = match target.get_array("buildPhases") {
+    pub fn embedded_extensions(&self, target_uuid: &str) -> Vec<EmbeddedExtension> {
+        let phases = match self.get_object(target_uuid).and_then(|t| t.get_array("buildPhases")) {
             Some(p) => p,
             None => return vec![],
         };
 
-        let mut embedded_file_refs: Vec<&str> = Vec::new();
+        let product_targets: HashMap<&str, &str> = self
+            .native_targets()
+            .into_iter()
+            .filter_map(|t| Some((t.get_str("productReference")?, t.uuid.as_str())))
+            .collect();
+
+        let mut result = Vec::new();
         for phase_val in phases {
-            let phase_uuid = match phase_val.as_str() {
-                Some(u) => u,
-                None => continue,
-            };
-            let phase = match self.get_object(phase_uuid) {
+            let phase = match phase_val.as_str().and_then(|u| self.get_object(u)) {
                 Some(p) if p.isa == "PBXCopyFilesBuildPhase" => p,
                 _ => continue,
             };
@@ -986,25 +2896,17 @@ impl XcodeProject {
                 None => continue,
             };
             for file_val in files {
-                if let Some(build_file_uuid) = file_val.as_str() {
-                    if let Some(build_file) = self.get_object(build_file_uuid) {
-                        if let Some(file_ref) = build_file.get_str("fileRef") {
-                            embedded_file_refs.push(file_ref);
-                        }
-                    }
-                }
-            }
-        }
-
-        if embedded_file_refs.is_empty() {
-            return vec![];
-        }
-
-        let mut result = Vec::new();
-        for t in self.native_targets() {
-            if let Some(product_ref) = t.get_str("productReference") {
-                if embedded_file_refs.contains(&product_ref) {
-                    result.push(t.uuid.clone());
+                let file_ref = file_val
+                    .as_str()
+                    .and_then(|u| self.get_object(u))
+                    .and_then(|build_file| build_file.get_str("fileRef"));
+                if let Some(&embedded_uuid) = file_ref.and_then(|r| product_targets.get(r)) {
+                    result.push(EmbeddedExtension {
+                        target_uuid: embedded_uuid.to_string(),
+                        phase_uuid: phase.uuid.clone(),
+                        dst_subfolder_spec: phase.get_int("dstSubfolderSpec"),
+                        phase_name: phase.get_str("name").map(String::from),
+                    });
                 }
             }
         }
@@ -1016,29 +2918,30 @@ impl XcodeProject {
     /// Creates a PBXCopyFilesBuildPhase with the correct dstSubfolderSpec
     /// based on the extension's product type, creates a PBXBuildFile
     /// referencing the extension's product, and wires everything to the
-    /// host target.
+    /// host target. Like Xcode, the host also gets a target dependency on the
+    /// extension (reusing an existing one) so the extension is built first.
     ///
-    /// Returns the UUID of the PBXCopyFilesBuildPhase.
-    pub fn embed_extension(&mut self, host_target_uuid: &str, extension_target_uuid: &str) -> Option<String> {
+    /// Returns None without changing the project if either UUID isn't a target
+    /// or the extension has no product.
+    pub fn embed_extension(
+        &mut self,
+        host_target_uuid: &str,
+        extension_target_uuid: &str,
+    ) -> Option<ExtensionEmbedding> {
+        let is_target = |uuid: &str| {
+            self.get_object(uuid)
+                .is_some_and(|obj| obj.isa().is_some_and(|isa| isa.is_target()))
+        };
+        if !is_target(host_target_uuid) || !is_target(extension_target_uuid) {
+            return None;
+        }
+
         // Get extension target's product type and product reference
         let ext_target = self.get_object(extension_target_uuid)?;
         let product_type = ext_target.get_str("productType")?.to_string();
         let product_ref_uuid = ext_target.get_str("productReference")?.to_string();
 
-        // Determine dstSubfolderSpec and phase name from product type
-        let (dst_subfolder_spec, dst_path, phase_name) = match product_type.as_str() {
-            "com.apple.product-type.application.on-demand-install-capable" => {
-                (16, "$(CONTENTS_FOLDER_PATH)/AppClips", "Embed App Clips")
-            }
-            "com.apple.product-type.application" => (16, "$(CONTENTS_FOLDER_PATH)/Watch", "Embed Watch Content"),
-            "com.apple.product-type.extensionkit-extension" => {
-                (16, "$(EXTENSIONS_FOLDER_PATH)", "Embed ExtensionKit Extensions")
-            }
-            _ => {
-                // Default: PlugIns folder for app extensions
-                (13, "", "Embed Foundation Extensions")
-            }
-        };
+        let (dst_subfolder_spec, dst_path, phase_name) = embed_phase_for(&product_type);
 
         // Create PBXBuildFile referencing the extension product
         let mut build_file_props = PlistMap::default();
@@ -1062,7 +2965,7 @@ impl XcodeProject {
         phase_props.insert(Cow::Owned("dstSubfolderSpec".to_string()), PlistValue::Integer(dst_subfolder_spec));
         phase_props.insert(
             Cow::Owned("files".to_string()),
-            PlistValue::Array(vec![PlistValue::String(Cow::Owned(build_file_uuid))]),
+            PlistValue::Array(vec![PlistValue::String(Cow::Owned(build_file_uuid.clone()))]),
         );
         phase_props.insert(Cow::Owned("name".to_string()), PlistValue::String(Cow::Owned(phase_name.to_string())));
         phase_props.insert(Cow::Owned("runOnlyForDeploymentPostprocessing".to_string()), PlistValue::Integer(0));
@@ -1075,7 +2978,308 @@ impl XcodeProject {
             }
         }
 
-        Some(phase_uuid)
+        let dependency_uuid = match self.find_dependency(host_target_uuid, extension_target_uuid) {
+            Some(uuid) => uuid,
+            None => self.add_dependency(host_target_uuid, extension_target_uuid)?,
+        };
+
+        Some(ExtensionEmbedding {
+            phase_uuid,
+            build_file_uuid,
+            dependency_uuid,
+        })
+    }
+
+    /// Undo [`embed_extension`](Self::embed_extension): remove the host's copy-files
+    /// build file for the extension's product, and the host's dependency on the extension.
+    ///
+    /// The copy-files phase is removed too once empty, if it carries the name
+    /// `embed_extension` gives it. Returns false if the product wasn't embedded.
+    pub fn unembed_extension(&mut self, host_target_uuid: &str, extension_target_uuid: &str) -> bool {
+        let Some(ext_target) = self.get_object(extension_target_uuid) else {
+            return false;
+        };
+        let Some(product_ref_uuid) = ext_target.get_str("productReference").map(String::from) else {
+            return false;
+        };
+        let (_, _, default_phase_name) = embed_phase_for(ext_target.get_str("productType").unwrap_or(""));
+
+        let host_phases: Vec<String> = self
+            .get_object(host_target_uuid)
+            .and_then(|host| host.get_array("buildPhases"))
+            .map(|phases| phases.iter().filter_map(|p| p.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+
+        let mut embeddings: Vec<(String, Vec<String>)> = Vec::new();
+        for phase_uuid in host_phases {
+            let Some(phase) = self
+                .get_object(&phase_uuid)
+                .filter(|p| p.isa == "PBXCopyFilesBuildPhase")
+            else {
+                continue;
+            };
+            let build_files: Vec<String> = phase
+                .get_array("files")
+                .into_iter()
+                .flatten()
+                .filter_map(|f| f.as_str())
+                .filter(|f| self.get_object(f).and_then(|bf| bf.get_str("fileRef")) == Some(product_ref_uuid.as_str()))
+                .map(String::from)
+                .collect();
+            if !build_files.is_empty() {
+                embeddings.push((phase_uuid, build_files));
+            }
+        }
+        if embeddings.is_empty() {
+            return false;
+        }
+
+        for (phase_uuid, build_files) in embeddings {
+            for build_file in build_files {
+                self.remove_object(&build_file);
+            }
+            let phase = self.get_object(&phase_uuid);
+            let is_empty = phase
+                .and_then(|p| p.get_array("files"))
+                .is_none_or(|files| files.is_empty());
+            if is_empty && phase.and_then(|p| p.get_str("name")) == Some(default_phase_name) {
+                self.remove_object(&phase_uuid);
+            }
+        }
+
+        if let Some(dependency_uuid) = self.find_dependency(host_target_uuid, extension_target_uuid) {
+            if let Some(proxy_uuid) = self
+                .get_object(&dependency_uuid)
+                .and_then(|d| d.get_str("targetProxy"))
+                .map(String::from)
+            {
+                self.delete_object(&proxy_uuid);
+            }
+            self.remove_object(&dependency_uuid);
+        }
+        true
+    }
+
+    // ── Localization ───────────────────────────────────────────────
+
+    /// The root project's `developmentRegion`, e.g. `"en"`.
+    pub fn development_region(&self) -> Option<&str> {
+        self.root_object()?.get_str("developmentRegion")
+    }
+
+    /// Set the root project's `developmentRegion`. Returns false if there is no root object.
+    pub fn set_development_region(&mut self, region: &str) -> bool {
+        let Some(root) = self.root_object_mut() else {
+            return false;
+        };
+        root.set_sorted("developmentRegion", PlistValue::String(Cow::Owned(region.to_string())));
+        true
+    }
+
+    /// The regions listed in the root project's `knownRegions`, in file order.
+    pub fn known_regions(&self) -> Vec<String> {
+        self.root_object()
+            .and_then(|root| root.get_array("knownRegions"))
+            .map(|regions| regions.iter().filter_map(|r| r.as_str().map(String::from)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Append a region to `knownRegions`, creating the array if needed.
+    /// Returns false if the region is already known or there is no root object.
+    pub fn add_known_region(&mut self, region: &str) -> bool {
+        let Some(root) = self.root_object_mut() else {
+            return false;
+        };
+        let value = PlistValue::String(Cow::Owned(region.to_string()));
+        match root.props.get_mut("knownRegions") {
+            Some(PlistValue::Array(regions)) => {
+                if regions.iter().any(|r| r.as_str() == Some(region)) {
+                    return false;
+                }
+                regions.push(value);
+            }
+            _ => root.set_sorted("knownRegions", PlistValue::Array(vec![value])),
+        }
+        true
+    }
+
+    /// Remove a region from `knownRegions`. Returns false if it wasn't listed.
+    pub fn remove_known_region(&mut self, region: &str) -> bool {
+        let Some(PlistValue::Array(regions)) = self
+            .root_object_mut()
+            .and_then(|root| root.props.get_mut("knownRegions"))
+        else {
+            return false;
+        };
+        let before = regions.len();
+        regions.retain(|r| r.as_str() != Some(region));
+        regions.len() != before
+    }
+
+    /// Add a localized variant (e.g. `fr.lproj/Main.strings`) to a `PBXVariantGroup`,
+    /// naming the reference after its region as Xcode does.
+    ///
+    /// With `register_region`, the region is also added to `knownRegions`.
+    /// Returns the UUID of the new PBXFileReference, or None if `variant_group_uuid`
+    /// isn't a variant group.
+    pub fn add_variant_group_language(
+        &mut self,
+        variant_group_uuid: &str,
+        region: &str,
+        path: &str,
+        register_region: bool,
+    ) -> Option<String> {
+        if self.get_object(variant_group_uuid)?.isa != "PBXVariantGroup" {
+            return None;
+        }
+        let options = FileOptions {
+            name: Some(region.to_string()),
+            ..FileOptions::default()
+        };
+        let file_uuid = self.add_file_with_options(variant_group_uuid, path, options)?;
+        if register_region {
+            self.add_known_region(region);
+        }
+        Some(file_uuid)
+    }
+
+    // ── Project attributes ─────────────────────────────────────────
+
+    /// The Xcode version (e.g. `"1630"`) that last offered to upgrade the project's
+    /// settings, from the root project's `attributes.LastUpgradeCheck`.
+    pub fn last_upgrade_check(&self) -> Option<String> {
+        self.project_attribute("LastUpgradeCheck")
+    }
+
+    /// Set `attributes.LastUpgradeCheck`, e.g. after applying recommended settings.
+    pub fn set_last_upgrade_check(&mut self, version: &str) -> bool {
+        self.set_project_attribute("LastUpgradeCheck", version)
+    }
+
+    /// The Xcode version that last checked the project's Swift code for migration,
+    /// from `attributes.LastSwiftUpdateCheck`.
+    pub fn last_swift_update_check(&self) -> Option<String> {
+        self.project_attribute("LastSwiftUpdateCheck")
+    }
+
+    /// Set `attributes.LastSwiftUpdateCheck`.
+    pub fn set_last_swift_update_check(&mut self, version: &str) -> bool {
+        self.set_project_attribute("LastSwiftUpdateCheck", version)
+    }
+
+    /// Read a scalar from the root project's `attributes` as text; integers such as
+    /// `LastUpgradeCheck = 1130` are formatted as written.
+    fn project_attribute(&self, key: &str) -> Option<String> {
+        match self
+            .root_object()?
+            .get_object("attributes")?
+            .iter()
+            .find(|(k, _)| k == key)?
+            .1
+        {
+            PlistValue::String(ref s) => Some(s.to_string()),
+            PlistValue::Integer(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Set a string in the root project's `attributes`, inserting new keys in
+    /// alphabetical order as Xcode does.
+    fn set_project_attribute(&mut self, key: &str, value: &str) -> bool {
+        let Some(root) = self.root_object_mut() else {
+            return false;
+        };
+        if !matches!(root.props.get("attributes"), Some(PlistValue::Object(_))) {
+            root.set_sorted("attributes", PlistValue::Object(Vec::new()));
+        }
+        let Some(PlistValue::Object(attributes)) = root.props.get_mut("attributes") else {
+            return false;
+        };
+        let value = PlistValue::String(Cow::Owned(value.to_string()));
+        match attributes.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => {
+                let index = attributes
+                    .iter()
+                    .position(|(k, _)| k.as_ref() > key)
+                    .unwrap_or(attributes.len());
+                attributes.insert(index, (Cow::Owned(key.to_string()), value));
+            }
+        }
+        true
+    }
+
+    // ── Swift packages ─────────────────────────────────────────────
+
+    /// Add an XCRemoteSwiftPackageReference and list it in the root project's `packageReferences`.
+    ///
+    /// Returns the UUID of the package reference.
+    pub fn add_swift_package(&mut self, repository_url: &str, requirement: SwiftPackageRequirement) -> Option<String> {
+        self.root_object()?;
+
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("XCRemoteSwiftPackageReference".to_string())),
+        );
+        props.insert(
+            Cow::Owned("repositoryURL".to_string()),
+            PlistValue::String(Cow::Owned(repository_url.to_string())),
+        );
+        props.insert(Cow::Owned("requirement".to_string()), requirement.to_plist());
+        let package_uuid = self.create_object(props);
+
+        let root = self.root_object_mut()?;
+        let entry = PlistValue::String(Cow::Owned(package_uuid.clone()));
+        if let Some(PlistValue::Array(ref mut refs)) = root.props.get_mut("packageReferences") {
+            refs.push(entry);
+        } else {
+            root.set_sorted("packageReferences", PlistValue::Array(vec![entry]));
+        }
+
+        Some(package_uuid)
+    }
+
+    /// List every Swift package reference with its requirement and the products wired to targets.
+    pub fn swift_packages(&self) -> Vec<SwiftPackageInfo> {
+        let targets = self.target_uuids();
+        let mut packages = Vec::new();
+        for (uuid, obj) in self.objects.iter() {
+            let (kind, location_key) = match obj.isa.as_str() {
+                "XCRemoteSwiftPackageReference" => (SwiftPackageKind::Remote, "repositoryURL"),
+                "XCLocalSwiftPackageReference" => (SwiftPackageKind::Local, "relativePath"),
+                _ => continue,
+            };
+            let products = self
+                .objects
+                .iter()
+                .filter(|(_, dep)| dep.isa == "XCSwiftPackageProductDependency" && dep.get_str("package") == Some(uuid))
+                .map(|(dep_uuid, dep)| SwiftPackageProduct {
+                    uuid: dep_uuid.clone(),
+                    product_name: dep.get_str("productName").unwrap_or_default().to_string(),
+                    targets: targets
+                        .iter()
+                        .filter(|t| {
+                            self.get_object(t)
+                                .and_then(|target| target.get_array("packageProductDependencies"))
+                                .is_some_and(|deps| deps.iter().any(|d| d.as_str() == Some(dep_uuid.as_str())))
+                        })
+                        .cloned()
+                        .collect(),
+                })
+                .collect();
+            packages.push(SwiftPackageInfo {
+                uuid: uuid.clone(),
+                kind,
+                location: obj.get_str(location_key).unwrap_or_default().to_string(),
+                requirement: obj
+                    .props
+                    .get("requirement")
+                    .and_then(SwiftPackageRequirement::from_plist),
+                products,
+            });
+        }
+        packages
     }
 
     // ── Xcode 16+ file system sync groups ──────────────────────────
@@ -1125,6 +3329,113 @@ impl XcodeProject {
         Some(sync_group_uuid)
     }
 
+    /// Add a file inside a synchronized folder to a target through the folder's
+    /// `PBXFileSystemSynchronizedBuildFileExceptionSet` for that target, which is how
+    /// Xcode 16 adds single files from a folder the target doesn't otherwise build.
+    ///
+    /// `path` is relative to the sync group. The target's exception set is created if
+    /// needed; paths are kept sorted, as Xcode writes them. Returns the UUID of the
+    /// exception set, or an error if `sync_group_uuid` isn't a sync group or `target_uuid`
+    /// isn't a target.
+    ///
+    /// If the target already lists the group in `fileSystemSynchronizedGroups`, it builds the
+    /// whole folder and its exception set excludes files instead. The path is then removed
+    /// from that set, whose UUID is returned; `Ok(None)` means the target already builds the
+    /// file and nothing changed.
+    pub fn add_file_to_sync_target(
+        &mut self,
+        sync_group_uuid: &str,
+        target_uuid: &str,
+        path: &str,
+    ) -> Result<Option<String>, String> {
+        let group = self
+            .get_object(sync_group_uuid)
+            .ok_or_else(|| format!("Sync group {} not found", sync_group_uuid))?;
+        if group.isa != "PBXFileSystemSynchronizedRootGroup" {
+            return Err(format!(
+                "{} {} is not a synchronized root group",
+                group.isa, sync_group_uuid
+            ));
+        }
+        let target = self
+            .get_object(target_uuid)
+            .filter(|target| target.isa().is_some_and(|isa| isa.is_target()))
+            .ok_or_else(|| format!("Target {} not found", target_uuid))?;
+        let attached = target
+            .references("fileSystemSynchronizedGroups")
+            .contains(&sync_group_uuid);
+        let existing = group
+            .get_array("exceptions")
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+            .find(|uuid| {
+                self.get_object(uuid).is_some_and(|set| {
+                    set.isa == "PBXFileSystemSynchronizedBuildFileExceptionSet"
+                        && set.get_str("target") == Some(target_uuid)
+                })
+            })
+            .map(String::from);
+
+        if attached {
+            let Some(set_uuid) = existing else {
+                return Ok(None);
+            };
+            let Some(PlistValue::Array(paths)) = self
+                .get_object_mut(&set_uuid)
+                .and_then(|set| set.props.get_mut("membershipExceptions"))
+            else {
+                return Ok(None);
+            };
+            let before = paths.len();
+            paths.retain(|p| p.as_str() != Some(path));
+            return Ok((paths.len() < before).then_some(set_uuid));
+        }
+
+        let path_value = PlistValue::String(Cow::Owned(path.to_string()));
+        if let Some(set_uuid) = existing {
+            if let Some(set) = self.get_object_mut(&set_uuid) {
+                match set.props.get_mut("membershipExceptions") {
+                    Some(PlistValue::Array(paths)) => {
+                        if !paths.iter().any(|p| p.as_str() == Some(path)) {
+                            let index = paths
+                                .iter()
+                                .position(|p| p.as_str().is_some_and(|p| p > path))
+                                .unwrap_or(paths.len());
+                            paths.insert(index, path_value);
+                        }
+                    }
+                    _ => set.set_sorted("membershipExceptions", PlistValue::Array(vec![path_value])),
+                }
+            }
+            return Ok(Some(set_uuid));
+        }
+
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXFileSystemSynchronizedBuildFileExceptionSet".to_string())),
+        );
+        props.insert(
+            Cow::Owned("membershipExceptions".to_string()),
+            PlistValue::Array(vec![path_value]),
+        );
+        props.insert(
+            Cow::Owned("target".to_string()),
+            PlistValue::String(Cow::Owned(target_uuid.to_string())),
+        );
+        let set_uuid = self.create_object(props);
+
+        if let Some(group) = self.get_object_mut(sync_group_uuid) {
+            let set_ref = PlistValue::String(Cow::Owned(set_uuid.clone()));
+            match group.props.get_mut("exceptions") {
+                Some(PlistValue::Array(exceptions)) => exceptions.push(set_ref),
+                _ => group.set_sorted("exceptions", PlistValue::Array(vec![set_ref])),
+            }
+        }
+        Ok(Some(set_uuid))
+    }
+
     /// Get the `path` of each `PBXFileSystemSynchronizedRootGroup` linked to a
     /// target's `fileSystemSynchronizedGroups` array.
     /// Returns `[]` if the target has no sync groups (pre-Xcode 16 projects).
@@ -1147,12 +3458,8 @@ impl XcodeProject {
 
     /// Remove a build setting from all configurations for a target.
     pub fn remove_build_setting(&mut self, target_uuid: &str, key: &str) -> bool {
-        let target = match self.get_object(target_uuid) {
-            Some(t) => t,
-            None => return false,
-        };
-        let config_list_uuid = match target.get_str("buildConfigurationList") {
-            Some(s) => s.to_string(),
+        let config_list_uuid = match self.target_config_list(target_uuid) {
+            Some(s) => s,
             None => return false,
         };
         let config_list = match self.get_object(&config_list_uuid) {
@@ -1173,73 +3480,368 @@ impl XcodeProject {
         }
         true
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use std::path::Path;
 
-    const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+    /// Remove a build setting from every configuration of every target, e.g. to strip a
+    /// deprecated setting like `ENABLE_BITCODE` project-wide.
+    /// Returns the number of configurations that had the setting.
+    pub fn remove_build_setting_all_targets(&mut self, key: &str) -> usize {
+        let mut config_uuids: Vec<String> = Vec::new();
+        for target_uuid in self.target_uuids() {
+            let configs = self
+                .target_config_list(&target_uuid)
+                .and_then(|list_uuid| self.get_object(&list_uuid))
+                .and_then(|list| list.get_array("buildConfigurations"))
+                .map(|arr| {
+                    arr.iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            for config_uuid in configs {
+                if !config_uuids.contains(&config_uuid) {
+                    config_uuids.push(config_uuid);
+                }
+            }
+        }
 
-    #[test]
-    fn test_open_project() {
-        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
-        let content = fs::read_to_string(&path).unwrap();
-        let project = XcodeProject::from_plist(&content).unwrap();
+        let mut changed = 0;
+        for config_uuid in config_uuids {
+            if let Some(config) = self.get_object_mut(&config_uuid) {
+                if let Some(PlistValue::Object(ref mut settings)) = config.props.get_mut("buildSettings") {
+                    let before = settings.len();
+                    settings.retain(|(k, _)| k.as_ref() != key);
+                    if settings.len() != before {
+                        changed += 1;
+                    }
+                }
+            }
+        }
+        changed
+    }
 
-        assert_eq!(project.archive_version, 1);
-        assert_eq!(project.object_version, 46);
-        assert!(!project.root_object_uuid.is_empty());
-        assert!(project.root_object().is_some());
+    /// Add the default project build settings for `variant` to a configuration's `buildSettings`.
+    ///
+    /// Settings the configuration already defines are left untouched; missing ones are
+    /// appended in alphabetical order. Returns false if `config_uuid` isn't an `XCBuildConfiguration`.
+    pub fn apply_default_settings(&mut self, config_uuid: &str, variant: ConfigVariant) -> bool {
+        let config = match self.storage_mut().get_mut(config_uuid) {
+            Some(c) if c.isa == "XCBuildConfiguration" => c,
+            _ => return false,
+        };
+        if !matches!(config.props.get("buildSettings"), Some(PlistValue::Object(_))) {
+            config
+                .props
+                .insert(Cow::Owned("buildSettings".to_string()), PlistValue::Object(Vec::new()));
+        }
+        if let Some(PlistValue::Object(ref mut settings)) = config.props.get_mut("buildSettings") {
+            let defaults = ProjectDefaultBuildSettings::for_variant(variant);
+            let mut keys: Vec<&str> = defaults
+                .keys()
+                .copied()
+                .filter(|k| !settings.iter().any(|(existing, _)| existing == k))
+                .collect();
+            keys.sort_unstable();
+            for key in keys {
+                settings.push((
+                    Cow::Owned(key.to_string()),
+                    PlistValue::String(Cow::Owned(defaults[key].to_string())),
+                ));
+            }
+        }
+        true
     }
+}
 
-    #[test]
-    fn test_objects_by_isa() {
-        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
-        let content = fs::read_to_string(&path).unwrap();
-        let project = XcodeProject::from_plist(&content).unwrap();
+/// Borrowing JSON view of a project, laid out like [`XcodeProject::to_plist`].
+struct JsonView<'p>(&'p XcodeProject);
+
+impl Serialize for JsonView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let project = self.0;
+        let mut map = serializer.serialize_map(Some(5))?;
+        map.serialize_entry("archiveVersion", &project.archive_version)?;
+        map.serialize_entry("classes", &PairsView(&project.classes))?;
+        map.serialize_entry("objectVersion", &project.object_version)?;
+        map.serialize_entry("objects", &ObjectsView(&project.objects))?;
+        map.serialize_entry("rootObject", &project.root_object_uuid)?;
+        map.end()
+    }
+}
 
-        let targets = project.native_targets();
-        assert!(!targets.is_empty());
+/// Serializes a `PlistObject` as a map, like `PlistValue::Object`.
+struct PairsView<'p>(&'p PlistObject<'static>);
 
-        let groups = project.objects_by_isa("PBXGroup");
-        assert!(!groups.is_empty());
+impl Serialize for PairsView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k.as_ref(), v)))
     }
+}
 
-    #[test]
-    fn test_get_referrers() {
-        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
-        let content = fs::read_to_string(&path).unwrap();
-        let project = XcodeProject::from_plist(&content).unwrap();
+/// Serializes the object map as UUID → properties.
+struct ObjectsView<'p>(&'p IndexMap<String, PbxObject>);
 
-        // The root object's mainGroup should be referenced by the root object
-        if let Some(main_group_uuid) = project.main_group_uuid() {
-            let referrers = project.get_referrers(&main_group_uuid);
-            assert!(!referrers.is_empty());
+impl Serialize for ObjectsView<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(uuid, obj)| (uuid, &obj.props)))
+    }
+}
+
+/// Recursively sort the keys of every JSON map in `value`.
+fn sort_json_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.sort_keys();
+            map.values_mut().for_each(sort_json_keys);
         }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sort_json_keys),
+        _ => {}
     }
+}
 
-    #[test]
-    fn test_roundtrip_via_xcode_project() {
-        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
-        let original = fs::read_to_string(&path).unwrap();
-        let project = XcodeProject::from_plist(&original).unwrap();
-        let output = project.to_pbxproj();
-        assert_eq!(output, original);
+/// Assemble a conditional build setting key: `KEY[condition]`, or `KEY` if `condition` is empty.
+fn conditional_key(key: &str, condition: &str) -> String {
+    if condition.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}[{}]", key, condition)
     }
+}
 
-    #[test]
-    fn test_unique_id() {
-        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
-        let content = fs::read_to_string(&path).unwrap();
-        let project = XcodeProject::from_plist(&content).unwrap();
+/// Split `KEY[condition]` into its base key and condition.
+fn split_conditional_key(key: &str) -> (&str, Option<&str>) {
+    match key.find('[') {
+        Some(open) if key.ends_with(']') => (&key[..open], Some(&key[open + 1..key.len() - 1])),
+        _ => (key, None),
+    }
+}
 
-        let id1 = project.get_unique_id("test-seed");
-        let id2 = project.get_unique_id("test-seed");
-        assert_eq!(id1, id2); // Same seed, same result
-        assert_eq!(id1.len(), 24);
+/// Whether `isa` is a group that lists `children`. Unlike [`Isa::is_group`], this
+/// leaves out synchronized folders, whose contents come from disk.
+fn is_children_group(isa: &str) -> bool {
+    matches!(isa, "PBXGroup" | "PBXVariantGroup" | "XCVersionGroup")
+}
+
+/// `(dstSubfolderSpec, dstPath, phase name)` of the copy-files phase that embeds a
+/// product of `product_type`; app extensions go to PlugIns.
+fn embed_phase_for(product_type: &str) -> (i64, &'static str, &'static str) {
+    match product_type {
+        "com.apple.product-type.application.on-demand-install-capable" => {
+            (16, "$(CONTENTS_FOLDER_PATH)/AppClips", "Embed App Clips")
+        }
+        "com.apple.product-type.application" => (16, "$(CONTENTS_FOLDER_PATH)/Watch", "Embed Watch Content"),
+        "com.apple.product-type.extensionkit-extension" => {
+            (16, "$(EXTENSIONS_FOLDER_PATH)", "Embed ExtensionKit Extensions")
+        }
+        _ => (13, "", "Embed Foundation Extensions"),
+    }
+}
+
+/// Replace `path` with `contents` via a temp file in the same directory and a rename,
+/// keeping the permissions of any existing file.
+///
+/// The temp name combines the process ID with a per-process counter, so concurrent saves
+/// of the same path from different threads don't write into each other's temp file.
+fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SAVE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", path.display()))?;
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        SAVE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = (|| {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        if let Ok(metadata) = std::fs::metadata(path) {
+            std::fs::set_permissions(&tmp_path, metadata.permissions())?;
+        }
+        std::fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+/// Upper bound on variable lookups while resolving one setting in
+/// [`XcodeProject::resolved_build_settings`].
+const MAX_SETTING_LOOKUPS: usize = 256;
+
+/// Text of a build setting value as Xcode passes it on; array items are joined with spaces.
+fn setting_words(value: &PlistValue<'_>) -> Option<String> {
+    match value {
+        PlistValue::Array(items) => Some(items.iter().filter_map(setting_text).collect::<Vec<_>>().join(" ")),
+        _ => setting_text(value),
+    }
+}
+
+/// Text of a scalar build setting value; numbers are formatted as written.
+fn setting_text(value: &PlistValue<'_>) -> Option<String> {
+    match value {
+        PlistValue::String(s) => Some(s.to_string()),
+        PlistValue::Integer(n) => Some(n.to_string()),
+        PlistValue::Float(f) => Some(f.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a dotted version like `5.0` or `10.13` into its numeric components.
+fn parse_version(text: &str) -> Option<Vec<u32>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let parts: Vec<u32> = text.split('.').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    // Compare 12 and 12.0 as equal.
+    let len = parts.iter().rposition(|&p| p != 0).map_or(1, |i| i + 1);
+    Some(parts[..len].to_vec())
+}
+
+/// Infer `lastKnownFileType` from a path's extension.
+fn infer_file_type(path: &str) -> &'static str {
+    let ext = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    crate::types::constants::FILE_TYPES_BY_EXTENSION
+        .get(ext)
+        .copied()
+        .unwrap_or("file")
+}
+
+/// Built-in reference keys of `obj` followed by those registered for its ISA in `extra`.
+fn reference_keys_with<'a>(obj: &PbxObject, extra: &'a HashMap<String, Vec<String>>) -> Vec<&'a str> {
+    let mut keys: Vec<&'a str> = obj.reference_keys();
+    for key in extra.get(&obj.isa).into_iter().flatten() {
+        if !keys.contains(&key.as_str()) {
+            keys.push(key);
+        }
+    }
+    keys
+}
+
+/// Replace backslash path separators with `/`, or `None` if there are none.
+///
+/// Only a backslash followed by an ordinary path character counts as a separator;
+/// `\ `, `\"`, `\'`, `\$` and `\\` are escapes, and a trailing `\` has nothing to separate.
+fn normalize_separators(path: &str) -> Option<String> {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars().peekable();
+    let mut changed = false;
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.peek() {
+            Some(' ' | '"' | '\'' | '$' | '\\') => {
+                out.push(ch);
+                out.extend(chars.next());
+            }
+            Some(_) => {
+                out.push('/');
+                changed = true;
+            }
+            None => out.push(ch),
+        }
+    }
+    changed.then_some(out)
+}
+
+/// Replace `$(inherited)` in a setting with `inherited`, collapsing the whitespace an
+/// empty or padded substitution leaves behind. Values without `$(inherited)` are kept as is.
+fn expand_inherited(value: &str, inherited: &str) -> String {
+    if !value.contains("$(inherited)") {
+        return value.to_string();
+    }
+    value
+        .replace("$(inherited)", inherited)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Infer the `sourceTree` a new reference of the given file type should use.
+fn infer_source_tree(file_type: &str) -> &'static str {
+    crate::types::constants::SOURCETREE_BY_FILETYPE
+        .get(file_type)
+        .copied()
+        .unwrap_or("<group>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+    #[test]
+    fn test_open_project() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+
+        assert_eq!(project.archive_version, 1);
+        assert_eq!(project.object_version, 46);
+        assert!(!project.root_object_uuid.is_empty());
+        assert!(project.root_object().is_some());
+    }
+
+    #[test]
+    fn test_objects_by_isa() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+
+        let targets = project.native_targets();
+        assert!(!targets.is_empty());
+
+        let groups = project.objects_by_isa("PBXGroup");
+        assert!(!groups.is_empty());
+    }
+
+    #[test]
+    fn test_get_referrers() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+
+        // The root object's mainGroup should be referenced by the root object
+        if let Some(main_group_uuid) = project.main_group_uuid() {
+            let referrers = project.get_referrers(&main_group_uuid);
+            assert!(!referrers.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_via_xcode_project() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let original = fs::read_to_string(&path).unwrap();
+        let project = XcodeProject::from_plist(&original).unwrap();
+        let output = project.to_pbxproj();
+        assert_eq!(output, original);
+    }
+
+    #[test]
+    fn test_unique_id() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+
+        let id1 = project.get_unique_id("test-seed");
+        let id2 = project.get_unique_id("test-seed");
+        assert_eq!(id1, id2); // Same seed, same result
+        assert_eq!(id1.len(), 24);
 
         let id3 = project.get_unique_id("different-seed");
         assert_ne!(id1, id3);
@@ -1392,4 +3994,2361 @@ mod tests {
         let output = project.to_pbxproj();
         assert!(output.contains("PBXResourcesBuildPhase"));
     }
+
+    #[test]
+    fn test_add_build_rule_round_trip() {
+        let path = Path::new(FIXTURES_DIR).join("Cocoa-Application.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+
+        let target_uuid = project.native_targets()[0].uuid.clone();
+        let rule_uuid = project
+            .add_build_rule(
+                &target_uuid,
+                "*.proto",
+                "protoc --swift_out=$DERIVED_FILE_DIR $INPUT_FILE_PATH\n",
+                vec!["$(DERIVED_FILE_DIR)/$(INPUT_FILE_BASE).pb.swift".to_string()],
+            )
+            .unwrap();
+
+        let reparsed = XcodeProject::from_plist(&project.to_pbxproj()).unwrap();
+        let rule = reparsed.get_object(&rule_uuid).unwrap();
+        assert_eq!(rule.isa, "PBXBuildRule");
+        assert_eq!(rule.get_str("compilerSpec"), Some("com.apple.compilers.proxy.script"));
+        assert_eq!(rule.get_str("filePatterns"), Some("*.proto"));
+        assert_eq!(rule.get_str("fileType"), Some("pattern.proxy"));
+        assert_eq!(rule.get_array("outputFiles").unwrap().len(), 1);
+
+        let rules = reparsed
+            .get_object(&target_uuid)
+            .unwrap()
+            .get_array("buildRules")
+            .unwrap();
+        assert!(rules.iter().any(|r| r.as_str() == Some(rule_uuid.as_str())));
+        assert!(reparsed.find_orphaned_references().is_empty());
+
+        assert!(project
+            .add_build_rule("nonexistent-uuid", "*.proto", "", vec![])
+            .is_none());
+    }
+
+    #[test]
+    fn test_retag_group_to_variant_group() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let group_uuid = project.main_group_uuid().unwrap();
+        let children = project.get_group_children(&group_uuid);
+
+        assert!(!project.retag_object(&group_uuid, "PBXNotAThing"));
+        assert!(!project.retag_object("000000000000000000000000", "PBXVariantGroup"));
+        assert!(project.retag_object(&group_uuid, "PBXVariantGroup"));
+
+        let obj = project.get_object(&group_uuid).unwrap();
+        assert_eq!(obj.isa, "PBXVariantGroup");
+        assert_eq!(project.get_group_children(&group_uuid), children);
+
+        let output = project.to_pbxproj();
+        let section = output.split("/* Begin PBXVariantGroup section */").nth(1).unwrap();
+        let section = section.split("/* End PBXVariantGroup section */").next().unwrap();
+        assert!(section.contains(&group_uuid));
+    }
+
+    #[test]
+    fn test_retag_drops_inapplicable_references() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let group_uuid = project.main_group_uuid().unwrap();
+
+        assert!(project.retag_object(&group_uuid, "PBXFileReference"));
+        assert!(project.get_object(&group_uuid).unwrap().props.get("children").is_none());
+    }
+
+    #[test]
+    fn test_reachable_from_target() {
+        let content = fs::read_to_string(format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        // The share extension has no dependencies, so the app target isn't reachable from it.
+        let by_name = |name: &str| {
+            project
+                .target_uuids()
+                .into_iter()
+                .find(|t| project.get_target_name(t).as_deref() == Some(name))
+                .unwrap()
+        };
+        let app_uuid = by_name("multitarget");
+        let ext_uuid = by_name("shareextension");
+        let target = project.get_object(&ext_uuid).unwrap();
+
+        let reachable = project.reachable_from(&ext_uuid);
+        assert!(reachable.contains(&ext_uuid));
+        assert!(reachable.contains(target.get_str("buildConfigurationList").unwrap()));
+        for phase in target.get_array("buildPhases").unwrap() {
+            let phase_uuid = phase.as_str().unwrap();
+            assert!(reachable.contains(phase_uuid));
+            for file in project.get_object(phase_uuid).unwrap().get_array("files").unwrap() {
+                assert!(reachable.contains(file.as_str().unwrap()));
+            }
+        }
+        assert!(!reachable.contains(&app_uuid));
+        assert!(project.reachable_from(&app_uuid).contains(&ext_uuid));
+        assert!(project.reachable_from("000000000000000000000000").is_empty());
+    }
+
+    #[test]
+    fn test_to_json_string_matches_to_json() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let expected = serde_json::to_string(&project.to_json().unwrap()).unwrap();
+        assert_eq!(project.to_json_string(false).unwrap(), expected);
+
+        let pretty = project.to_json_string(true).unwrap();
+        assert!(pretty.contains('\n'));
+        let reparsed: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(reparsed, project.to_json().unwrap());
+    }
+
+    #[test]
+    fn test_swift_packages() {
+        let content = fs::read_to_string(format!("{}/006-spm.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let packages = project.swift_packages();
+        assert_eq!(packages.len(), 1);
+
+        let package = &packages[0];
+        assert_eq!(package.uuid, "AC9C55BC2BD9246500041977");
+        assert_eq!(package.kind, SwiftPackageKind::Remote);
+        assert_eq!(package.location, "https://github.com/supabase/supabase-swift");
+        assert_eq!(
+            package.requirement,
+            Some(SwiftPackageRequirement::UpToNextMajorVersion("2.5.1".to_string()))
+        );
+
+        assert_eq!(package.products.len(), 1);
+        let product = &package.products[0];
+        assert_eq!(product.uuid, "AC9C55BD2BD9246500041977");
+        assert_eq!(product.product_name, "Supabase");
+        assert_eq!(product.targets.len(), 1);
+        assert_eq!(
+            project.get_target_name(&product.targets[0]).as_deref(),
+            Some("watchApp")
+        );
+
+        let spm_free = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        assert!(XcodeProject::from_plist(&spm_free).unwrap().swift_packages().is_empty());
+    }
+
+    #[test]
+    fn test_add_swift_package_requirements_round_trip() {
+        let mut project = XcodeProject::new_empty("Packages", None);
+        let range = SwiftPackageRequirement::VersionRange {
+            minimum: "1.2.0".to_string(),
+            maximum: "2.0.0".to_string(),
+        };
+        let pinned = SwiftPackageRequirement::Revision("4b6e3f2a9c1d".to_string());
+        let range_uuid = project
+            .add_swift_package("https://github.com/example/range", range.clone())
+            .unwrap();
+        let pinned_uuid = project
+            .add_swift_package("https://github.com/example/pinned", pinned.clone())
+            .unwrap();
+
+        let output = project.to_pbxproj();
+        assert!(
+            output.contains("kind = versionRange;\n\t\t\t\tmaximumVersion = 2.0.0;\n\t\t\t\tminimumVersion = 1.2.0;")
+        );
+        assert!(output.contains("kind = revision;\n\t\t\t\trevision = 4b6e3f2a9c1d;"));
+
+        let reparsed = XcodeProject::from_plist(&output).unwrap();
+        let refs = reparsed.root_object().unwrap().get_array("packageReferences").unwrap();
+        assert_eq!(refs.len(), 2);
+        let packages = reparsed.swift_packages();
+        let requirement_of = |uuid: &str| {
+            packages
+                .iter()
+                .find(|p| p.uuid == uuid)
+                .and_then(|p| p.requirement.clone())
+        };
+        assert_eq!(requirement_of(&range_uuid), Some(range));
+        assert_eq!(requirement_of(&pinned_uuid), Some(pinned));
+
+        for requirement in [
+            SwiftPackageRequirement::UpToNextMinorVersion("5.1.0".to_string()),
+            SwiftPackageRequirement::ExactVersion("1.0.0".to_string()),
+            SwiftPackageRequirement::Branch("main".to_string()),
+        ] {
+            assert_eq!(
+                SwiftPackageRequirement::from_plist(&requirement.to_plist()),
+                Some(requirement)
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_json_matches_to_json() {
+        let content = fs::read_to_string(format!("{}/AFNetworking.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let expected = project.to_json().unwrap();
+
+        let mut compact = Vec::new();
+        project.write_json(&mut compact, false).unwrap();
+        assert_eq!(
+            String::from_utf8(compact.clone()).unwrap(),
+            project.to_json_string(false).unwrap()
+        );
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&compact).unwrap(), expected);
+
+        let mut pretty = Vec::new();
+        project.write_json(&mut pretty, true).unwrap();
+        assert!(pretty.contains(&b'\n'));
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&pretty).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_open_xcodeproj() {
+        let root = std::env::temp_dir().join(format!("xcode-open-xcodeproj-{}", std::process::id()));
+        let bundle = root.join("App.xcodeproj");
+        fs::create_dir_all(&bundle).unwrap();
+        fs::copy(
+            format!("{}/project.pbxproj", FIXTURES_DIR),
+            bundle.join("project.pbxproj"),
+        )
+        .unwrap();
+        let pbxproj = bundle.join("project.pbxproj").to_string_lossy().to_string();
+
+        let from_dir = XcodeProject::open_xcodeproj(&bundle.to_string_lossy()).unwrap();
+        assert_eq!(from_dir.file_path(), Some(pbxproj.as_str()));
+        assert_eq!(from_dir.get_project_root(), Some(root.to_string_lossy().to_string()));
+        from_dir.save().unwrap();
+
+        let from_file = XcodeProject::open_xcodeproj(&pbxproj).unwrap();
+        assert_eq!(from_file.file_path(), Some(pbxproj.as_str()));
+        assert_eq!(from_file.to_pbxproj(), from_dir.to_pbxproj());
+
+        assert!(XcodeProject::open_xcodeproj(&root.join("Missing.xcodeproj").to_string_lossy()).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_save_as_keeps_file_path() {
+        let source = format!("{}/project.pbxproj", FIXTURES_DIR);
+        let project = XcodeProject::open(&source).unwrap();
+        let copy = std::env::temp_dir().join(format!("xcode-save-as-{}.pbxproj", std::process::id()));
+
+        project.save_as(&copy.to_string_lossy()).unwrap();
+        assert_eq!(project.file_path(), Some(source.as_str()));
+
+        let reparsed = XcodeProject::open(&copy.to_string_lossy()).unwrap();
+        assert_eq!(reparsed.to_pbxproj(), project.to_pbxproj());
+        fs::remove_file(&copy).unwrap();
+    }
+
+    #[test]
+    fn test_save_is_atomic_and_keeps_permissions() {
+        let dir = std::env::temp_dir().join(format!("xcode-atomic-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.pbxproj");
+        fs::copy(format!("{}/project.pbxproj", FIXTURES_DIR), &path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+        }
+
+        let mut project = XcodeProject::open(&path.to_string_lossy()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        project.add_file(&main_group, "Saved.swift").unwrap();
+        project.save().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), project.to_pbxproj());
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("project.pbxproj")]);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_saves_use_distinct_temp_files() {
+        let dir = std::env::temp_dir().join(format!("xcode-concurrent-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("project.pbxproj");
+        fs::copy(format!("{}/project.pbxproj", FIXTURES_DIR), &path).unwrap();
+        let project = XcodeProject::open(&path.to_string_lossy()).unwrap();
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| project.save().unwrap());
+            }
+        });
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), project.to_pbxproj());
+        let entries: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("project.pbxproj")]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_deduplicate_file_references() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        assert_eq!(project.deduplicate_file_references(), 0);
+
+        // A child listed twice that has nothing to do with a merge stays listed twice.
+        let main_group = project.main_group_uuid().unwrap();
+        let repeated = project.get_group_children(&main_group)[0].clone();
+        if let Some(PlistValue::Array(children)) =
+            project.get_object_mut(&main_group).unwrap().props.get_mut("children")
+        {
+            children.push(PlistValue::String(Cow::Owned(repeated.clone())));
+        }
+        let repeats = |project: &XcodeProject| {
+            project
+                .get_group_children(&main_group)
+                .iter()
+                .filter(|c| **c == repeated)
+                .count()
+        };
+        assert_eq!(repeats(&project), 2);
+        assert_eq!(project.deduplicate_file_references(), 0);
+        assert_eq!(repeats(&project), 2);
+
+        // Clone a file reference that has a build file, then point the build file and group at the clone.
+        let build_file_uuid = project.objects_by_isa("PBXBuildFile")[0].uuid.clone();
+        let original_uuid = project
+            .get_object(&build_file_uuid)
+            .unwrap()
+            .get_str("fileRef")
+            .unwrap()
+            .to_string();
+        let group_uuid = project
+            .get_referrers(&original_uuid)
+            .iter()
+            .find(|o| o.isa == "PBXGroup")
+            .unwrap()
+            .uuid
+            .clone();
+        let duplicate_uuid = project.create_object(project.get_object(&original_uuid).unwrap().props.clone());
+        project
+            .get_object_mut(&build_file_uuid)
+            .unwrap()
+            .set_str("fileRef", &duplicate_uuid);
+        if let Some(PlistValue::Array(children)) =
+            project.get_object_mut(&group_uuid).unwrap().props.get_mut("children")
+        {
+            children.push(PlistValue::String(Cow::Owned(duplicate_uuid.clone())));
+        }
+        let children_before = project.get_group_children(&group_uuid).len();
+
+        assert_eq!(project.deduplicate_file_references(), 1);
+        assert!(project.get_object(&duplicate_uuid).is_none());
+        assert_eq!(
+            project.get_object(&build_file_uuid).unwrap().get_str("fileRef"),
+            Some(original_uuid.as_str())
+        );
+        let children = project.get_group_children(&group_uuid);
+        assert_eq!(children.len(), children_before - 1);
+        assert_eq!(children.iter().filter(|c| **c == original_uuid).count(), 1);
+        assert!(project.find_orphaned_references().is_empty());
+        assert_eq!(repeats(&project), 2);
+    }
+
+    #[test]
+    fn test_new_empty_project() {
+        let mut project = XcodeProject::new_empty("MyApp", Some("Acme"));
+        assert_eq!(project.object_version, LAST_KNOWN_OBJECT_VERSION);
+        let main_group = project.main_group_uuid().unwrap();
+        let products = project.product_ref_group_uuid().unwrap();
+        assert_eq!(project.get_group_children(&main_group), vec![products]);
+        let config_list = project.build_configuration_list_uuid().unwrap();
+        let release = project.get_default_configuration(&config_list).unwrap();
+        let settings = release.get_object("buildSettings").unwrap();
+        assert!(settings
+            .iter()
+            .any(|(k, v)| k == "ALWAYS_SEARCH_USER_PATHS" && v.as_str() == Some("NO")));
+        assert!(settings
+            .iter()
+            .any(|(k, v)| k == "VALIDATE_PRODUCT" && v.as_str() == Some("YES")));
+
+        let target = project
+            .create_native_target("MyApp", "com.apple.product-type.application", "com.acme.myapp")
+            .unwrap();
+        assert_eq!(project.target_uuids(), vec![target.clone()]);
+        assert!(project.find_orphaned_references().is_empty());
+        let target_config_list = project.target_config_list(&target).unwrap();
+        let target_configs: Vec<String> = project
+            .get_object(&target_config_list)
+            .unwrap()
+            .get_array("buildConfigurations")
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect();
+        for (config_uuid, only_active_arch) in target_configs.iter().zip([Some("YES"), None]) {
+            let settings = project
+                .get_object(config_uuid)
+                .unwrap()
+                .get_object("buildSettings")
+                .unwrap();
+            let get = |key: &str| settings.iter().find(|(k, _)| k == key).and_then(|(_, v)| v.as_str());
+            assert_eq!(get("ALWAYS_SEARCH_USER_PATHS"), Some("NO"));
+            assert_eq!(get("ONLY_ACTIVE_ARCH"), only_active_arch);
+            assert_eq!(get("PRODUCT_BUNDLE_IDENTIFIER"), Some("com.acme.myapp"));
+            assert!(settings.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        }
+
+        let reparsed = XcodeProject::from_plist(&project.to_pbxproj()).unwrap();
+        assert_eq!(reparsed.target_uuids(), project.target_uuids());
+        assert!(reparsed.find_orphaned_references().is_empty());
+        let attributes = reparsed
+            .root_object()
+            .unwrap()
+            .get_object("attributes")
+            .unwrap()
+            .clone();
+        assert!(attributes
+            .iter()
+            .any(|(k, v)| k == "ORGANIZATIONNAME" && v.as_str() == Some("Acme")));
+    }
+
+    #[test]
+    fn test_apply_default_settings() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let target = project.target_uuids()[0].clone();
+        let config_list = project
+            .get_object(&target)
+            .unwrap()
+            .get_str("buildConfigurationList")
+            .unwrap()
+            .to_string();
+        let config_uuid = project.get_default_configuration(&config_list).unwrap().uuid.clone();
+        let product_name_before = project.get_build_setting(&target, "PRODUCT_NAME");
+
+        assert!(project.apply_default_settings(&config_uuid, ConfigVariant::Release));
+        let settings = project
+            .get_object(&config_uuid)
+            .unwrap()
+            .get_object("buildSettings")
+            .unwrap();
+        let get = |key: &str| settings.iter().find(|(k, _)| k == key).and_then(|(_, v)| v.as_str());
+        assert_eq!(get("ALWAYS_SEARCH_USER_PATHS"), Some("NO"));
+        assert_eq!(get("VALIDATE_PRODUCT"), Some("YES"));
+        assert_eq!(get("ONLY_ACTIVE_ARCH"), None);
+        assert_eq!(project.get_build_setting(&target, "PRODUCT_NAME"), product_name_before);
+
+        assert!(!project.apply_default_settings(&target, ConfigVariant::Debug));
+    }
+
+    #[test]
+    fn test_contains() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        assert!(project.contains(&project.root_object_uuid));
+        assert!(project.contains(&project.main_group_uuid().unwrap()));
+        assert!(!project.contains("000000000000000000000000"));
+        assert!(!project.contains(""));
+    }
+
+    #[test]
+    fn test_isa_histogram() {
+        let content = fs::read_to_string(format!("{}/AFNetworking.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let histogram = project.isa_histogram();
+
+        assert_eq!(histogram.values().sum::<usize>(), project.objects().count());
+        assert_eq!(histogram.get("PBXProject"), Some(&1));
+        assert_eq!(
+            histogram.get("PBXBuildFile"),
+            Some(&project.objects_by_isa("PBXBuildFile").len())
+        );
+        let counts: Vec<usize> = histogram.values().copied().collect();
+        assert!(counts.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn test_add_file_with_options() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let group = project.main_group_uuid().unwrap();
+
+        let options = FileOptions {
+            source_tree: Some("SOURCE_ROOT".to_string()),
+            ..FileOptions::default()
+        };
+        let uuid = project
+            .add_file_with_options(&group, "Config/Shared.xcconfig", options)
+            .unwrap();
+        let file = project.get_object(&uuid).unwrap();
+        assert_eq!(file.get_str("sourceTree"), Some("SOURCE_ROOT"));
+        assert_eq!(file.get_str("lastKnownFileType"), Some("text.xcconfig"));
+        assert_eq!(file.get_str("name"), Some("Shared.xcconfig"));
+        assert!(project.get_group_children(&group).contains(&uuid));
+
+        let options = FileOptions {
+            file_type: Some("text".to_string()),
+            name: Some("Notes".to_string()),
+            ..FileOptions::default()
+        };
+        let uuid = project.add_file_with_options(&group, "NOTES.md", options).unwrap();
+        let file = project.get_object(&uuid).unwrap();
+        assert_eq!(file.get_str("lastKnownFileType"), Some("text"));
+        assert_eq!(file.get_str("name"), Some("Notes"));
+        assert_eq!(file.get_str("sourceTree"), Some("<group>"));
+    }
+
+    #[test]
+    fn test_add_file_idempotent() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let group = project.main_group_uuid().unwrap();
+        let refs_before = project.objects_by_isa("PBXFileReference").len();
+
+        let first = project.add_file_idempotent(&group, "Sources/Feature.swift").unwrap();
+        let second = project.add_file_idempotent(&group, "Sources/Feature.swift").unwrap();
+        assert_eq!(first, second);
+        assert_eq!(project.objects_by_isa("PBXFileReference").len(), refs_before + 1);
+        assert_eq!(
+            project
+                .get_group_children(&group)
+                .iter()
+                .filter(|c| **c == first)
+                .count(),
+            1
+        );
+
+        let other = project.add_file_idempotent(&group, "Sources/Other.swift").unwrap();
+        assert_ne!(other, first);
+    }
+
+    #[test]
+    fn test_for_each_object_mut() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let config_count = project.objects_by_isa("XCBuildConfiguration").len();
+        assert!(config_count > 1);
+
+        let mut visited = 0;
+        project.for_each_object_mut("XCBuildConfiguration", |config| {
+            visited += 1;
+            if let Some(PlistValue::Object(settings)) = config.props.get_mut("buildSettings") {
+                settings.retain(|(k, _)| k != "ENABLE_BITCODE");
+                settings.push((Cow::Borrowed("ENABLE_BITCODE"), PlistValue::String(Cow::Borrowed("NO"))));
+            }
+        });
+        assert_eq!(visited, config_count);
+
+        for config in project.objects_by_isa("XCBuildConfiguration") {
+            let settings = config.get_object("buildSettings").unwrap();
+            let bitcode: Vec<_> = settings.iter().filter(|(k, _)| k == "ENABLE_BITCODE").collect();
+            assert_eq!(bitcode.len(), 1);
+            assert_eq!(bitcode[0].1.as_str(), Some("NO"));
+        }
+        assert!(project
+            .objects_by_isa("PBXGroup")
+            .iter()
+            .all(|g| g.get_object("buildSettings").is_none()));
+    }
+
+    #[test]
+    fn test_comment_for_matches_serialized_comments() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let output = project.to_pbxproj();
+
+        let build_file = project.find_objects_by_isa("PBXBuildFile")[0].clone();
+        let comment = project.comment_for(&build_file).unwrap();
+        assert!(comment.contains(" in "));
+        assert!(output.contains(&format!("{} /* {} */", build_file, comment)));
+
+        let config_list = project.find_objects_by_isa("XCConfigurationList")[0].clone();
+        let comment = project.comment_for(&config_list).unwrap();
+        assert!(comment.starts_with("Build configuration list for "));
+        assert!(output.contains(&format!("{} /* {} */", config_list, comment)));
+
+        assert_eq!(project.comment_for("MISSING"), None);
+    }
+
+    #[test]
+    fn test_comment_for_agrees_with_writer() {
+        for fixture in [
+            "swift-protobuf.pbxproj",
+            "006-spm.pbxproj",
+            "009-expo-app-clip.pbxproj",
+            "watch.pbxproj",
+        ] {
+            let content = fs::read_to_string(format!("{}/{}", FIXTURES_DIR, fixture)).unwrap();
+            let project = XcodeProject::from_plist(&content).unwrap();
+            let plist = project.to_plist();
+            for (uuid, _) in project.objects() {
+                assert_eq!(
+                    project.comment_for(uuid),
+                    comments::get_comment_for_uuid(&plist, uuid),
+                    "{} in {}",
+                    uuid,
+                    fixture
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_plist_rejects_array_root() {
+        let err = XcodeProject::from_plist("( a, b, c )").unwrap_err();
+        assert!(err.contains("got an array"));
+    }
+
+    #[test]
+    fn test_lint_build_settings() {
+        let content = fs::read_to_string(format!("{}/project-rn74.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        assert!(project.lint_build_settings().is_empty());
+
+        let target = project.target_uuids()[0].clone();
+        let target_name = project.get_target_name(&target);
+        let setting = |v: &'static str| PlistValue::String(Cow::Borrowed(v));
+        project.set_build_setting(&target, "SWIFT_VERSION", setting("five"));
+        project.set_build_setting(&target, "IPHONEOS_DEPLOYMENT_TARGET", setting("9.0"));
+        project.set_build_setting(&target, "ENABLE_BITCODE", setting("$(INHERITED_BITCODE)"));
+
+        let warnings = project.lint_build_settings();
+        let keys: HashSet<&str> = warnings.iter().map(|w| w.key.as_str()).collect();
+        assert_eq!(keys, HashSet::from(["SWIFT_VERSION", "IPHONEOS_DEPLOYMENT_TARGET"]));
+        assert!(warnings.iter().all(|w| w.target == target_name && !w.config.is_empty()));
+        assert!(warnings.iter().any(|w| w.message.contains("minimum of 12.0")));
+    }
+
+    #[test]
+    fn test_set_object_value() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let root = project.root_object_uuid.clone();
+
+        assert!(project.set_object_value(&root, "hasScannedForEncodings", PlistValue::Integer(1)));
+        let regions = PlistValue::Array(vec![
+            PlistValue::String(Cow::Borrowed("en")),
+            PlistValue::String(Cow::Borrowed("fr")),
+        ]);
+        assert!(project.set_object_value(&root, "knownRegions", regions.clone()));
+        assert!(!project.set_object_value("MISSING", "key", PlistValue::Integer(1)));
+
+        let reparsed = XcodeProject::from_plist(&project.to_pbxproj()).unwrap();
+        let root = reparsed.root_object().unwrap();
+        assert_eq!(root.props.get("hasScannedForEncodings"), Some(&PlistValue::Integer(1)));
+        assert_eq!(root.props.get("knownRegions"), Some(&regions));
+    }
+
+    #[test]
+    fn test_size_delta() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        assert_eq!(project.original_len(), content.len());
+        let expected = project.to_pbxproj().len() as i64 - content.len() as i64;
+        assert_eq!(project.size_delta(), expected);
+
+        let before = project.size_delta();
+        project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+        assert!(project.size_delta() > before);
+        assert!(project.size_delta() > 0);
+    }
+
+    #[test]
+    fn test_conditional_build_settings() {
+        let content = fs::read_to_string(format!("{}/project-swift.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let root = project.root_object_uuid.clone();
+
+        let identity = project.get_build_setting_conditional(&root, "CODE_SIGN_IDENTITY", "sdk=iphoneos*");
+        assert_eq!(identity.as_ref().and_then(|v| v.as_str()), Some("iPhone Developer"));
+        assert_eq!(
+            project.list_conditions_for(&root, "CODE_SIGN_IDENTITY"),
+            vec!["sdk=iphoneos*"]
+        );
+
+        let value = PlistValue::String(Cow::Borrowed("-DARM"));
+        assert!(project.set_build_setting_conditional(&root, "OTHER_SWIFT_FLAGS", "arch=arm64", value));
+        let flags = project.get_build_setting(&root, "OTHER_SWIFT_FLAGS[arch=arm64]");
+        assert_eq!(flags.as_ref().and_then(|v| v.as_str()), Some("-DARM"));
+        assert_eq!(
+            project.list_conditions_for(&root, "OTHER_SWIFT_FLAGS"),
+            vec!["arch=arm64"]
+        );
+        assert!(project
+            .to_pbxproj()
+            .contains("\"OTHER_SWIFT_FLAGS[arch=arm64]\" = \"-DARM\";"));
+
+        assert_eq!(
+            split_conditional_key("KEY[sdk=*][arch=arm64]"),
+            ("KEY", Some("sdk=*][arch=arm64"))
+        );
+        assert_eq!(split_conditional_key("KEY"), ("KEY", None));
+    }
+
+    #[test]
+    fn test_snapshot_copy_on_write() {
+        let content = fs::read_to_string(format!("{}/AFNetworking.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let original_output = project.to_pbxproj();
+
+        let mut snapshot = project.snapshot();
+        assert!(snapshot.shares_objects_with(&project));
+        snapshot.get_object(&project.root_object_uuid).unwrap();
+        assert!(snapshot.shares_objects_with(&project));
+
+        let main_group = snapshot.main_group_uuid().unwrap();
+        snapshot.add_file(&main_group, "Speculative.swift").unwrap();
+        assert!(!snapshot.shares_objects_with(&project));
+        assert_eq!(project.to_pbxproj(), original_output);
+        assert_eq!(snapshot.objects().count(), project.objects().count() + 1);
+    }
+
+    #[test]
+    fn test_add_source_file() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let target = project.target_uuids()[0].clone();
+        let group = project.main_group_uuid().unwrap();
+
+        let phase_files = |project: &XcodeProject, isa: &str| -> Vec<String> {
+            project
+                .find_build_phase(&target, isa)
+                .and_then(|phase| phase.get_array("files"))
+                .map(|files| files.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default()
+        };
+
+        let (swift_ref, swift_build) = project.add_source_file(&target, &group, "Feature.swift").unwrap();
+        let (png_ref, png_build) = project.add_source_file(&target, &group, "icon.png").unwrap();
+        let (framework_ref, framework_build) = project.add_source_file(&target, &group, "Kit.framework").unwrap();
+
+        assert!(phase_files(&project, "PBXSourcesBuildPhase").contains(&swift_build));
+        assert!(phase_files(&project, "PBXResourcesBuildPhase").contains(&png_build));
+        assert!(!phase_files(&project, "PBXSourcesBuildPhase").contains(&png_build));
+        assert_eq!(
+            project.get_object(&swift_build).unwrap().get_str("fileRef"),
+            Some(swift_ref.as_str())
+        );
+        assert_eq!(
+            project.get_object(&png_build).unwrap().get_str("fileRef"),
+            Some(png_ref.as_str())
+        );
+        assert!(project.get_group_children(&group).contains(&png_ref));
+        assert!(phase_files(&project, "PBXFrameworksBuildPhase").contains(&framework_build));
+        assert!(project.get_group_children(&group).contains(&framework_ref));
+
+        let objects_before = project.objects().count();
+        for path in ["Feature.h", "Config/Shared.xcconfig", "App/Info.plist"] {
+            assert!(project.add_source_file(&target, &group, path).is_none(), "{}", path);
+        }
+        assert_eq!(project.objects().count(), objects_before);
+
+        assert!(project.add_source_file("MISSING", &group, "Other.swift").is_none());
+        assert!(project.add_source_file(&group, &group, "Other.swift").is_none());
+        assert!(project.add_source_file(&target, &target, "Other.swift").is_none());
+        assert!(project.add_source_file(&target, "MISSING", "Other.swift").is_none());
+        assert_eq!(project.objects().count(), objects_before);
+    }
+
+    #[test]
+    fn test_rename_group() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+
+        let group = project.add_group(&main_group, "Feature").unwrap();
+        project.set_object_property(&group, "path", "Feature");
+        let file = project.add_file(&group, "View.swift").unwrap();
+        let full_path = |project: &XcodeProject| get_full_path(project, project.get_object(&file).unwrap()).unwrap();
+        assert!(full_path(&project).ends_with("Feature/View.swift"));
+
+        assert!(project.rename_group(&group, "Screens"));
+        assert_eq!(project.get_object_property(&group, "name").as_deref(), Some("Screens"));
+        assert_eq!(project.get_object_property(&group, "path").as_deref(), Some("Screens"));
+        assert_eq!(
+            project.get_object_property(&file, "path").as_deref(),
+            Some("View.swift")
+        );
+        assert!(full_path(&project).ends_with("Screens/View.swift"));
+
+        // A group whose name differs from its path only gets a new name.
+        let supporting = "BB2F792B24A3F905000567C9";
+        assert!(project.rename_group(supporting, "Support"));
+        assert_eq!(
+            project.get_object_property(supporting, "path").as_deref(),
+            Some("testproject/Supporting")
+        );
+
+        assert!(!project.rename_group(&file, "Nope"));
+    }
+
+    #[test]
+    fn test_full_path_of_with_base_path() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let app_delegate = "13B07FB01A68108700A75B9A";
+
+        assert_eq!(project.get_project_root(), None);
+        assert_eq!(
+            project.full_path_of(app_delegate).as_deref(),
+            Some("testproject/AppDelegate.m")
+        );
+
+        project.set_base_path("/virtual/app/");
+        assert_eq!(project.get_project_root().as_deref(), Some("/virtual/app"));
+        assert_eq!(
+            project.full_path_of(app_delegate).as_deref(),
+            Some("/virtual/app/testproject/AppDelegate.m")
+        );
+        assert_eq!(project.full_path_of("MISSING"), None);
+    }
+
+    #[test]
+    fn test_object_version_keys() {
+        let mut project = XcodeProject::new_empty("App", None);
+        assert_eq!(project.object_version, 77);
+        let output = project.to_pbxproj();
+        assert!(output.contains("\t\t\tminimizedProjectReferenceProxies = 1;\n\t\t\tpreferredProjectObjectVersion = 77;\n\t\t\tproductRefGroup"));
+
+        project.set_object_version(70);
+        let root = project.root_object().unwrap();
+        assert_eq!(root.get_int("preferredProjectObjectVersion"), Some(70));
+        assert_eq!(root.get_int("minimizedProjectReferenceProxies"), None);
+
+        project.set_object_version(56);
+        assert_eq!(
+            project.root_object().unwrap().get_int("preferredProjectObjectVersion"),
+            None
+        );
+
+        // Targets created in an existing v77 project fill in missing keys.
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        project.object_version = 77;
+        project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+        let root = project.root_object().unwrap();
+        assert_eq!(root.get_int("preferredProjectObjectVersion"), Some(77));
+        assert_eq!(root.get_int("minimizedProjectReferenceProxies"), Some(1));
+    }
+
+    #[test]
+    fn test_create_native_target_product_types() {
+        let mut project = XcodeProject::new_empty("App", None);
+        let cases = [
+            (
+                "Ext",
+                "com.apple.product-type.extensionkit-extension",
+                "Ext.appex",
+                "wrapper.app-extension",
+            ),
+            (
+                "Clip",
+                "com.apple.product-type.application.on-demand-install-capable",
+                "Clip.app",
+                "wrapper.application",
+            ),
+            (
+                "Stickers",
+                "com.apple.product-type.app-extension.messages-sticker-pack",
+                "Stickers.appex",
+                "wrapper.app-extension",
+            ),
+            (
+                "Kit",
+                "com.apple.product-type.framework.static",
+                "Kit.framework",
+                "wrapper.framework",
+            ),
+            (
+                "Helper",
+                "com.apple.product-type.xpc-service",
+                "Helper.xpc",
+                "wrapper.xpc-service",
+            ),
+        ];
+        for (name, product_type, path, file_type) in cases {
+            let target_uuid = project
+                .create_native_target(name, product_type, "com.example.target")
+                .unwrap();
+            let product_uuid = project
+                .get_object(&target_uuid)
+                .unwrap()
+                .get_str("productReference")
+                .unwrap()
+                .to_string();
+            let product = project.get_object(&product_uuid).unwrap();
+            assert_eq!(product.get_str("path"), Some(path));
+            assert_eq!(product.get_str("explicitFileType"), Some(file_type));
+        }
+
+        let before = project.objects().count();
+        assert!(project
+            .create_native_target("Mystery", "com.example.product-type.unknown", "com.example.mystery")
+            .is_none());
+        assert_eq!(project.objects().count(), before);
+        assert_eq!(project.target_uuids().len(), cases.len());
+    }
+
+    #[test]
+    fn test_test_bundle_product_file_type() {
+        let mut project = XcodeProject::new_empty("App", None);
+        let product_of = |project: &XcodeProject, target_uuid: &str| {
+            let product_uuid = project
+                .get_object(target_uuid)
+                .unwrap()
+                .get_str("productReference")
+                .unwrap();
+            project.get_object(product_uuid).unwrap().clone()
+        };
+
+        let tests = project
+            .create_native_target(
+                "AppTests",
+                "com.apple.product-type.unit-test-bundle",
+                "com.example.tests",
+            )
+            .unwrap();
+        let product = product_of(&project, &tests);
+        assert_eq!(product.get_str("path"), Some("AppTests.xctest"));
+        assert_eq!(product.get_str("explicitFileType"), Some("wrapper.cfbundle"));
+
+        let ext = project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+        assert_eq!(
+            product_of(&project, &ext).get_str("explicitFileType"),
+            Some("wrapper.app-extension")
+        );
+    }
+
+    #[test]
+    fn test_ensure_named_build_phase() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let app = project.find_main_app_target("ios").unwrap().uuid.clone();
+        let widget = project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+
+        let extensions = project.embed_extension(&app, &widget).unwrap().phase_uuid;
+        let frameworks = project
+            .ensure_named_build_phase(&app, "PBXCopyFilesBuildPhase", "Embed Frameworks")
+            .unwrap();
+        assert_ne!(frameworks, extensions);
+        project.set_object_value(&frameworks, "dstSubfolderSpec", PlistValue::Integer(10));
+
+        assert_eq!(
+            project.ensure_named_build_phase(&app, "PBXCopyFilesBuildPhase", "Embed Frameworks"),
+            Some(frameworks.clone())
+        );
+        assert_eq!(
+            project.ensure_named_build_phase(&app, "PBXCopyFilesBuildPhase", "Embed Foundation Extensions"),
+            Some(extensions.clone())
+        );
+        let copy_phases = project
+            .get_object(&app)
+            .unwrap()
+            .get_array("buildPhases")
+            .unwrap()
+            .iter()
+            .filter(|v| project.get_object(v.as_str().unwrap()).unwrap().isa == "PBXCopyFilesBuildPhase")
+            .count();
+        assert_eq!(copy_phases, 2);
+        assert!(project
+            .ensure_named_build_phase("000000000000000000000000", "PBXCopyFilesBuildPhase", "Embed Frameworks")
+            .is_none());
+    }
+
+    #[test]
+    fn test_object_count() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let section_total: usize = project.isa_histogram().values().sum();
+        assert_eq!(project.object_count(), section_total);
+        assert_eq!(project.object_count(), content.matches("isa = ").count());
+        assert!(!project.is_empty());
+
+        let mut emptied = project.clone();
+        for uuid in project.objects().map(|(uuid, _)| uuid) {
+            emptied.delete_object(uuid);
+        }
+        assert_eq!(emptied.object_count(), 0);
+        assert!(emptied.is_empty());
+        assert_eq!(project.object_count(), section_total);
+    }
+
+    #[test]
+    fn test_target_config_list() {
+        let content = fs::read_to_string(format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let targets = project.target_uuids();
+        let lists: Vec<String> = targets.iter().map(|t| project.target_config_list(t).unwrap()).collect();
+        assert_eq!(lists, vec!["13B07F931A680F5B00A75B9A", "6ADAD6EE26493A420001F56E"]);
+        for target in &targets {
+            assert_eq!(project.default_config_name(target).as_deref(), Some("Release"));
+        }
+
+        let root_uuid = project.root_object_uuid.clone();
+        assert_eq!(
+            project.target_config_list(&root_uuid),
+            project.build_configuration_list_uuid()
+        );
+
+        project.set_object_property(&lists[1], "defaultConfigurationName", "Debug");
+        assert_eq!(project.default_config_name(&targets[0]).as_deref(), Some("Release"));
+        assert_eq!(project.default_config_name(&targets[1]).as_deref(), Some("Debug"));
+
+        let main_group = project.main_group_uuid().unwrap();
+        assert_eq!(project.target_config_list(&main_group), None);
+        assert_eq!(project.default_config_name("000000000000000000000000"), None);
+    }
+
+    #[test]
+    fn test_copy_configuration() {
+        let content = fs::read_to_string(format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let targets = project.target_uuids();
+        let (app, share) = (targets[0].clone(), targets[1].clone());
+        let settings_of = |project: &XcodeProject, target: &str, name: &str| {
+            let list = project.target_config_list(target).unwrap();
+            let uuid = project.find_configuration(&list, name).unwrap();
+            project
+                .get_object(&uuid)
+                .unwrap()
+                .get_object("buildSettings")
+                .unwrap()
+                .clone()
+        };
+
+        let config_count = project.objects_by_isa("XCBuildConfiguration").len();
+        assert!(project.copy_configuration(&app, &share, "Release"));
+        assert_eq!(
+            settings_of(&project, &share, "Release"),
+            settings_of(&project, &app, "Release")
+        );
+        assert_eq!(project.objects_by_isa("XCBuildConfiguration").len(), config_count);
+
+        // Copying into a config the destination lacks creates it, with fresh settings storage.
+        let app_list = project.target_config_list(&app).unwrap();
+        let debug_uuid = project.find_configuration(&app_list, "Debug").unwrap();
+        project.set_object_property(&debug_uuid, "name", "Staging");
+        assert!(project.copy_configuration(&app, &share, "Staging"));
+        let share_list = project.target_config_list(&share).unwrap();
+        let staging_uuid = project.find_configuration(&share_list, "Staging").unwrap();
+        assert_ne!(staging_uuid, debug_uuid);
+        assert_eq!(
+            settings_of(&project, &share, "Staging"),
+            settings_of(&project, &app, "Staging")
+        );
+        assert!(project.find_orphaned_references().is_empty());
+
+        project.set_build_setting(&app, "SWIFT_VERSION", PlistValue::String(Cow::Borrowed("6.0")));
+        assert_ne!(
+            settings_of(&project, &share, "Staging"),
+            settings_of(&project, &app, "Staging")
+        );
+
+        assert!(!project.copy_configuration(&app, &share, "Profile"));
+        assert!(!project.copy_configuration(&app, "000000000000000000000000", "Release"));
+    }
+
+    #[test]
+    fn test_resolved_build_settings() {
+        let mut project = XcodeProject::new_empty("App", None);
+        let target = project
+            .create_native_target("App", "com.apple.product-type.application", "com.example.app")
+            .unwrap();
+        let root_uuid = project.root_object_uuid.clone();
+        let string = |s: &str| PlistValue::String(Cow::Owned(s.to_string()));
+        project.set_build_setting(&root_uuid, "OTHER_LDFLAGS", string("-ObjC $(inherited)"));
+        project.set_build_setting(&root_uuid, "ORG", string("example"));
+        project.set_build_setting(
+            &target,
+            "OTHER_LDFLAGS",
+            PlistValue::Array(vec![string("$(inherited)"), string("-lz")]),
+        );
+        project.set_build_setting(
+            &target,
+            "PRODUCT_BUNDLE_IDENTIFIER",
+            string("com.$(ORG).$(PRODUCT_NAME:lower)"),
+        );
+        project.set_build_setting(&target, "PRODUCT_NAME", string("$(TARGET_NAME)"));
+        project.set_build_setting_conditional(&target, "OTHER_LDFLAGS", "sdk=iphoneos*", string("-ios"));
+        project.set_build_setting(&target, "LOOP_A", string("a$(LOOP_B)"));
+        project.set_build_setting(&target, "LOOP_B", string("b$(LOOP_A)"));
+
+        let settings = project.resolved_build_settings(&target, "Release").unwrap();
+        assert_eq!(settings["PRODUCT_NAME"], "App");
+        assert_eq!(settings["PRODUCT_BUNDLE_IDENTIFIER"], "com.example.app");
+        assert_eq!(settings["OTHER_LDFLAGS"], "-ObjC -lz");
+        assert_eq!(settings["SWIFT_VERSION"], "5.0");
+        // Project-level settings show through.
+        assert_eq!(settings["ORG"], "example");
+        assert!(!settings.keys().any(|key| key.contains('[')));
+        // Cycles terminate instead of recursing forever.
+        assert!(settings["LOOP_A"].starts_with("ab"));
+
+        assert!(project.resolved_build_settings(&target, "Profile").is_none());
+        assert!(project
+            .resolved_build_settings("000000000000000000000000", "Release")
+            .is_none());
+    }
+
+    #[test]
+    fn test_create_legacy_target_round_trip() {
+        let mut project = XcodeProject::new_empty("App", None);
+        let target = project
+            .create_legacy_target("Native", "/usr/bin/make", "$(ACTION) -j8", Some("native"))
+            .unwrap();
+
+        let output = project.to_pbxproj();
+        assert!(output.contains("/* Begin PBXLegacyTarget section */"));
+        assert!(output.contains("Build configuration list for PBXLegacyTarget \"Native\""));
+
+        let reparsed = XcodeProject::from_plist(&output).unwrap();
+        assert_eq!(reparsed.target_uuids(), vec![target.clone()]);
+        let legacy = reparsed.get_object(&target).unwrap();
+        assert_eq!(legacy.isa, "PBXLegacyTarget");
+        assert_eq!(legacy.get_str("buildToolPath"), Some("/usr/bin/make"));
+        assert_eq!(legacy.get_str("buildArgumentsString"), Some("$(ACTION) -j8"));
+        assert_eq!(legacy.get_str("buildWorkingDirectory"), Some("native"));
+        assert_eq!(legacy.get_int("passBuildSettingsInEnvironment"), Some(1));
+        assert_eq!(reparsed.get_target_name(&target).as_deref(), Some("Native"));
+        assert_eq!(reparsed.default_config_name(&target).as_deref(), Some("Release"));
+        assert_eq!(
+            reparsed.resolved_build_settings(&target, "Debug").unwrap()["PRODUCT_NAME"],
+            "Native"
+        );
+        assert!(reparsed.find_orphaned_references().is_empty());
+        assert_eq!(reparsed.to_pbxproj(), output);
+
+        let plain = project
+            .create_legacy_target("Docs", "/bin/sh", "docs.sh", None)
+            .unwrap();
+        assert_eq!(
+            project.get_object(&plain).unwrap().get_str("buildWorkingDirectory"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clear_build_phase() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let target = project.find_main_app_target("ios").unwrap().uuid.clone();
+        let phase = project.find_build_phase(&target, "PBXResourcesBuildPhase").unwrap();
+        let phase_uuid = phase.uuid.clone();
+        let build_files: Vec<String> = phase.references("files").iter().map(|s| s.to_string()).collect();
+        let file_refs: Vec<String> = build_files
+            .iter()
+            .map(|uuid| {
+                project
+                    .get_object(uuid)
+                    .unwrap()
+                    .get_str("fileRef")
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert!(!build_files.is_empty());
+
+        assert_eq!(project.clear_build_phase(&phase_uuid), build_files.len());
+        assert!(project
+            .get_object(&phase_uuid)
+            .unwrap()
+            .get_array("files")
+            .unwrap()
+            .is_empty());
+        assert!(build_files.iter().all(|uuid| !project.contains(uuid)));
+        assert!(file_refs.iter().all(|uuid| project.contains(uuid)));
+        assert!(project.find_orphaned_references().is_empty());
+
+        assert_eq!(project.clear_build_phase(&phase_uuid), 0);
+        assert_eq!(project.clear_build_phase(&target), 0);
+    }
+
+    #[test]
+    fn test_from_json_round_trip() {
+        let content = fs::read_to_string(format!("{}/AFNetworking.pbxproj", FIXTURES_DIR)).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let json = project.to_json().unwrap();
+
+        let restored = XcodeProject::from_json(&json).unwrap();
+        assert_eq!(restored.to_json().unwrap(), json);
+        assert_eq!(restored.object_count(), project.object_count());
+        assert_eq!(restored.root_object_uuid, project.root_object_uuid);
+        assert_eq!(restored.to_pbxproj(), project.to_pbxproj());
+
+        let from_str = XcodeProject::from_json_str(&project.to_json_string(true).unwrap()).unwrap();
+        assert_eq!(from_str.to_pbxproj(), project.to_pbxproj());
+
+        assert!(XcodeProject::from_json_str("{ not json")
+            .unwrap_err()
+            .starts_with("Invalid project JSON"));
+        assert!(XcodeProject::from_json(&serde_json::json!({ "objects": {} })).is_err());
+    }
+
+    #[test]
+    fn test_iter_group_files() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+
+        let all = project.iter_group_files(&main_group);
+        assert_eq!(all.len(), project.objects_by_isa("PBXFileReference").len());
+        assert!(all
+            .iter()
+            .all(|uuid| project.get_object(uuid).unwrap().isa == "PBXFileReference"));
+
+        let outer = project.add_group(&main_group, "Outer").unwrap();
+        let inner = project.add_group(&outer, "Inner").unwrap();
+        let a = project.add_file(&outer, "Outer/A.swift").unwrap();
+        let b = project.add_file(&inner, "Outer/Inner/B.swift").unwrap();
+        // Inner was added to Outer before A, so its files come first.
+        assert_eq!(project.iter_group_files(&outer), vec![b.clone(), a.clone()]);
+        assert_eq!(project.iter_group_files(&inner), vec![b.clone()]);
+
+        // A group listing its own ancestor must not loop forever.
+        let mut children = project
+            .get_object(&inner)
+            .unwrap()
+            .get_array("children")
+            .unwrap()
+            .clone();
+        children.push(PlistValue::String(Cow::Owned(outer.clone())));
+        project.set_object_value(&inner, "children", PlistValue::Array(children));
+        assert_eq!(project.iter_group_files(&outer), vec![b.clone(), a]);
+        assert_eq!(project.iter_group_files(&b), vec![b]);
+        assert!(project.iter_group_files("000000000000000000000000").is_empty());
+    }
+
+    #[test]
+    fn test_find_cycles() {
+        for fixture in [
+            "project.pbxproj",
+            "project-multitarget.pbxproj",
+            "AFNetworking.pbxproj",
+            "watch.pbxproj",
+        ] {
+            let content = fs::read_to_string(format!("{}/{}", FIXTURES_DIR, fixture)).unwrap();
+            assert!(
+                XcodeProject::from_plist(&content).unwrap().find_cycles().is_empty(),
+                "{}",
+                fixture
+            );
+        }
+
+        // Detach Outer from the main group and list it under its own child instead.
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let outer = project.add_group(&main_group, "Outer").unwrap();
+        let inner = project.add_group(&outer, "Inner").unwrap();
+        let file = project.add_file(&inner, "Inner/File.swift").unwrap();
+        project.get_object_mut(&main_group).unwrap().remove_reference(&outer);
+        let mut children = project
+            .get_object(&inner)
+            .unwrap()
+            .get_array("children")
+            .unwrap()
+            .clone();
+        children.push(PlistValue::String(Cow::Owned(outer.clone())));
+        project.set_object_value(&inner, "children", PlistValue::Array(children));
+
+        let cycles = project.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        let mut members = cycles[0].clone();
+        members.sort();
+        let mut expected = vec![outer.clone(), inner.clone()];
+        expected.sort();
+        assert_eq!(members, expected);
+
+        // Path resolution and parent walks terminate on the cycle.
+        let file_obj = project.get_object(&file).unwrap().clone();
+        let parents = crate::project::paths::get_parents(&project, &file_obj);
+        assert_eq!(parents.len(), 2);
+        assert_eq!(project.full_path_of(&file).as_deref(), Some("Inner/File.swift"));
+        assert_eq!(
+            crate::project::paths::get_full_path(&project, &file_obj).as_deref(),
+            Some("Inner/File.swift")
+        );
+    }
+
+    #[test]
+    fn test_replace_reference() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let old_ref = project
+            .objects_by_isa("PBXFileReference")
+            .into_iter()
+            .find(|obj| project.get_referrers(&obj.uuid).len() > 1)
+            .unwrap()
+            .uuid
+            .clone();
+        let referrers: Vec<String> = project
+            .get_referrers(&old_ref)
+            .iter()
+            .map(|obj| obj.uuid.clone())
+            .collect();
+
+        let main_group = project.main_group_uuid().unwrap();
+        let new_ref = project.add_file(&main_group, "Regenerated.xcassets").unwrap();
+        let before = project.get_referrers(&new_ref).len();
+
+        assert_eq!(project.replace_reference(&old_ref, &new_ref), referrers.len());
+        assert!(project.get_referrers(&old_ref).is_empty());
+        for uuid in &referrers {
+            assert!(project.get_object(uuid).unwrap().is_referencing(&new_ref));
+        }
+        assert_eq!(project.get_referrers(&new_ref).len(), before + referrers.len());
+        assert!(project.contains(&old_ref));
+
+        assert_eq!(project.replace_reference("000000000000000000000000", &new_ref), 0);
+    }
+
+    #[test]
+    fn test_development_region_and_known_regions() {
+        let mut project = XcodeProject::open(&format!("{}/watch.pbxproj", FIXTURES_DIR)).unwrap();
+        assert_eq!(project.development_region(), Some("en"));
+        assert_eq!(project.known_regions(), vec!["en", "Base"]);
+
+        assert!(project.set_development_region("de"));
+        assert_eq!(project.development_region(), Some("de"));
+
+        assert!(project.add_known_region("de"));
+        assert!(!project.add_known_region("de"));
+        assert!(project.remove_known_region("en"));
+        assert!(!project.remove_known_region("en"));
+        assert_eq!(project.known_regions(), vec!["Base", "de"]);
+
+        let reparsed = XcodeProject::from_plist(&project.to_pbxproj()).unwrap();
+        assert_eq!(reparsed.development_region(), Some("de"));
+        assert_eq!(reparsed.known_regions(), vec!["Base", "de"]);
+    }
+
+    #[test]
+    fn test_add_variant_group_language_registers_region() {
+        let mut project = XcodeProject::open(&format!("{}/watch.pbxproj", FIXTURES_DIR)).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let group = project.add_group(&main_group, "Main.storyboard").unwrap();
+        assert!(project
+            .add_variant_group_language(&group, "fr", "fr.lproj/Main.strings", true)
+            .is_none());
+
+        assert!(project.retag_object(&group, "PBXVariantGroup"));
+        let fr = project
+            .add_variant_group_language(&group, "fr", "fr.lproj/Main.strings", true)
+            .unwrap();
+        assert_eq!(project.get_object(&fr).unwrap().get_str("name"), Some("fr"));
+        assert!(project.get_group_children(&group).contains(&fr));
+        assert!(project.known_regions().contains(&"fr".to_string()));
+
+        project
+            .add_variant_group_language(&group, "ja", "ja.lproj/Main.strings", false)
+            .unwrap();
+        assert!(!project.known_regions().contains(&"ja".to_string()));
+    }
+
+    #[test]
+    fn test_embed_extension_adds_dependency() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let app = project.find_main_app_target("ios").unwrap().uuid.clone();
+        let widget = project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+
+        let embedding = project.embed_extension(&app, &widget).unwrap();
+        let app_obj = project.get_object(&app).unwrap();
+        assert!(app_obj
+            .get_array("buildPhases")
+            .unwrap()
+            .iter()
+            .any(|p| p.as_str() == Some(&embedding.phase_uuid)));
+        assert!(app_obj
+            .get_array("dependencies")
+            .unwrap()
+            .iter()
+            .any(|d| d.as_str() == Some(&embedding.dependency_uuid)));
+
+        let phase = project.get_object(&embedding.phase_uuid).unwrap();
+        assert_eq!(phase.isa, "PBXCopyFilesBuildPhase");
+        assert_eq!(
+            phase.get_array("files").unwrap()[0].as_str(),
+            Some(embedding.build_file_uuid.as_str())
+        );
+        let dependency = project.get_object(&embedding.dependency_uuid).unwrap();
+        assert_eq!(dependency.get_str("target"), Some(widget.as_str()));
+
+        // Embedding again reuses the dependency instead of adding a second one
+        let again = project.embed_extension(&app, &widget).unwrap();
+        assert_eq!(again.dependency_uuid, embedding.dependency_uuid);
+
+        // A host that isn't a target gets nothing created for it
+        let objects_before = project.objects().count();
+        assert!(project.embed_extension("nonexistent-uuid", &widget).is_none());
+        assert!(project.embed_extension(&embedding.phase_uuid, &widget).is_none());
+        assert_eq!(project.objects().count(), objects_before);
+    }
+
+    #[test]
+    fn test_unembed_extension_leaves_no_orphans() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let app = project.find_main_app_target("ios").unwrap().uuid.clone();
+        let widget = project
+            .create_native_target("Widget", "com.apple.product-type.app-extension", "com.example.widget")
+            .unwrap();
+        assert!(!project.unembed_extension(&app, &widget));
+
+        let before_phases = project
+            .get_object(&app)
+            .unwrap()
+            .get_array("buildPhases")
+            .unwrap()
+            .clone();
+        let embedding = project.embed_extension(&app, &widget).unwrap();
+        let proxy = project
+            .get_object(&embedding.dependency_uuid)
+            .and_then(|d| d.get_str("targetProxy"))
+            .unwrap()
+            .to_string();
+
+        assert!(project.unembed_extension(&app, &widget));
+        assert!(project.get_embedded_targets(&app).is_empty());
+        assert_eq!(
+            project.get_object(&app).unwrap().get_array("buildPhases"),
+            Some(&before_phases)
+        );
+        for uuid in [
+            &embedding.phase_uuid,
+            &embedding.build_file_uuid,
+            &embedding.dependency_uuid,
+            &proxy,
+        ] {
+            assert!(project.get_object(uuid).is_none());
+        }
+        assert!(project.find_orphaned_references().is_empty());
+        assert!(!project.unembed_extension(&app, &widget));
+    }
+
+    #[test]
+    fn test_embedded_extensions_on_app_clip() {
+        let project = XcodeProject::open(&format!("{}/009-expo-app-clip.pbxproj", FIXTURES_DIR)).unwrap();
+        let host = project
+            .native_targets()
+            .into_iter()
+            .find(|t| t.get_str("name") == Some("testlaunchappclip"))
+            .unwrap()
+            .uuid
+            .clone();
+
+        let embedded = project.embedded_extensions(&host);
+        assert_eq!(embedded.len(), 1);
+        assert_eq!(embedded[0].phase_uuid, "XXF738C40EC3AA7C79D472XX");
+        assert_eq!(embedded[0].dst_subfolder_spec, Some(16));
+        assert_eq!(embedded[0].phase_name.as_deref(), Some("Embed App Clips"));
+        assert_eq!(
+            project.get_embedded_targets(&host),
+            vec![embedded[0].target_uuid.clone()]
+        );
+        assert_eq!(
+            project
+                .get_object(&embedded[0].target_uuid)
+                .unwrap()
+                .get_str("productType"),
+            Some("com.apple.product-type.application.on-demand-install-capable")
+        );
+
+        assert!(project.embedded_extensions(&embedded[0].target_uuid).is_empty());
+        assert!(project.embedded_extensions("nonexistent-uuid").is_empty());
+    }
+
+    #[test]
+    fn test_dependents_of() {
+        let project = XcodeProject::open(&format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        let app = "13B07F861A680F5B00A75B9A";
+        let share_extension = "6ADAD6E126493A420001F56E";
+        assert_eq!(project.get_dependencies(app), vec![share_extension]);
+        assert_eq!(project.dependents_of(share_extension), vec![app]);
+        assert!(project.dependents_of(app).is_empty());
+        assert!(project.dependents_of("nonexistent-uuid").is_empty());
+    }
+
+    #[test]
+    fn test_target_build_order_linear_chain() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let app = project.find_main_app_target("ios").unwrap().uuid.clone();
+        let kit = project
+            .create_native_target("Kit", "com.apple.product-type.framework", "com.example.kit")
+            .unwrap();
+        let core = project
+            .create_native_target("Core", "com.apple.product-type.framework", "com.example.core")
+            .unwrap();
+        project.add_dependency(&app, &kit).unwrap();
+        project.add_dependency(&kit, &core).unwrap();
+
+        let order = project.target_build_order().unwrap();
+        assert_eq!(order.len(), project.target_uuids().len());
+        let position = |uuid: &str| order.iter().position(|u| u == uuid).unwrap();
+        assert!(position(&core) < position(&kit));
+        assert!(position(&kit) < position(&app));
+    }
+
+    #[test]
+    fn test_target_build_order_reports_cycle() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let a = project
+            .create_native_target("A", "com.apple.product-type.framework", "com.example.a")
+            .unwrap();
+        let b = project
+            .create_native_target("B", "com.apple.product-type.framework", "com.example.b")
+            .unwrap();
+        project.add_dependency(&a, &b).unwrap();
+        project.add_dependency(&b, &a).unwrap();
+
+        assert_eq!(project.target_build_order(), Err(vec![a, b]));
+    }
+
+    #[test]
+    fn test_set_phase_flags() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let script = project.objects_by_isa("PBXShellScriptBuildPhase")[0].uuid.clone();
+        assert_eq!(
+            project
+                .get_object(&script)
+                .unwrap()
+                .get_int("runOnlyForDeploymentPostprocessing"),
+            Some(0)
+        );
+
+        assert!(project.set_phase_flags(&script, Some(true), Some(8)));
+        let phase = project.get_object(&script).unwrap();
+        assert_eq!(phase.get_int("runOnlyForDeploymentPostprocessing"), Some(1));
+        assert_eq!(phase.get_int("buildActionMask"), Some(8));
+
+        assert!(project.set_phase_flags(&script, None, Some(2147483647)));
+        let phase = project.get_object(&script).unwrap();
+        assert_eq!(phase.get_int("runOnlyForDeploymentPostprocessing"), Some(1));
+        assert_eq!(phase.get_int("buildActionMask"), Some(2147483647));
+
+        let output = project.to_pbxproj();
+        assert!(output.contains("runOnlyForDeploymentPostprocessing = 1;"));
+        let main_group = project.main_group_uuid().unwrap();
+        assert!(!project.set_phase_flags(&main_group, Some(true), None));
+    }
+
+    #[test]
+    fn test_target_type_predicates() {
+        let watch = XcodeProject::open(&format!("{}/watch.pbxproj", FIXTURES_DIR)).unwrap();
+        let cases = [
+            ("13B07F861A680F5B00A75B9A", true, false, false), // application
+            ("6AE385372757CBED00A7841A", true, false, false), // application.watchapp2
+            ("6AE385422757CBEF00A7841A", false, false, true), // watchkit2-extension
+            ("6AE385572757CBF000A7841A", false, true, false), // bundle.unit-test
+            ("6AE385612757CBF000A7841A", false, true, false), // bundle.ui-testing
+        ];
+        for (uuid, app, test, extension) in cases {
+            assert_eq!(watch.is_app_target(uuid), app, "{}", uuid);
+            assert_eq!(watch.is_test_target(uuid), test, "{}", uuid);
+            assert_eq!(watch.is_extension_target(uuid), extension, "{}", uuid);
+        }
+
+        let clip = XcodeProject::open(&format!("{}/009-expo-app-clip.pbxproj", FIXTURES_DIR)).unwrap();
+        let clip_target = clip
+            .find_target_by_product_type("com.apple.product-type.application.on-demand-install-capable")
+            .unwrap();
+        assert!(clip.is_app_target(&clip_target.uuid));
+
+        let multitarget = XcodeProject::open(&format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        assert!(multitarget.is_extension_target("6ADAD6E126493A420001F56E"));
+
+        let main_group = watch.main_group_uuid().unwrap();
+        assert!(!watch.is_app_target(&main_group));
+        assert!(!watch.is_test_target("nonexistent-uuid"));
+    }
+
+    #[test]
+    fn test_targets_groups_and_build_phases() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+
+        let targets: Vec<String> = project.targets().map(|t| t.uuid.clone()).collect();
+        assert_eq!(targets, project.target_uuids());
+
+        let main_group = project.main_group_uuid().unwrap();
+        let groups: Vec<&PbxObject> = project.groups().collect();
+        assert!(groups.iter().any(|g| g.uuid == main_group));
+        assert!(groups.iter().all(|g| g.isa.contains("Group")));
+
+        let phases = project.build_phases().count();
+        assert_eq!(
+            phases,
+            project.objects().filter(|(_, o)| o.isa.ends_with("BuildPhase")).count()
+        );
+
+        // Unknown ISAs are left out rather than being treated as some known type
+        let mut props = PlistMap::default();
+        props.insert(
+            Cow::Owned("isa".to_string()),
+            PlistValue::String(Cow::Owned("PBXFutureBuildPhase".to_string())),
+        );
+        project.create_object(props);
+        assert_eq!(project.build_phases().count(), phases);
+        assert_eq!(project.targets().count(), targets.len());
+    }
+
+    #[test]
+    fn test_add_core_ml_models() {
+        let content = fs::read_to_string(format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let target = project.target_uuids()[0].clone();
+        let group = project.main_group_uuid().unwrap();
+        let phase_files = |project: &XcodeProject, isa: &str| -> Vec<String> {
+            project
+                .find_build_phase(&target, isa)
+                .and_then(|phase| phase.get_array("files"))
+                .map(|files| files.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default()
+        };
+
+        let (model_ref, model_build) = project.add_source_file(&target, &group, "Classifier.mlmodel").unwrap();
+        let (package_ref, package_build) = project.add_source_file(&target, &group, "Detector.mlpackage").unwrap();
+
+        assert_eq!(
+            project.get_object(&model_ref).unwrap().get_str("lastKnownFileType"),
+            Some("file.mlmodel")
+        );
+        assert_eq!(
+            project.get_object(&package_ref).unwrap().get_str("lastKnownFileType"),
+            Some("folder.mlpackage")
+        );
+        assert!(phase_files(&project, "PBXSourcesBuildPhase").contains(&model_build));
+        assert!(phase_files(&project, "PBXResourcesBuildPhase").contains(&package_build));
+    }
+
+    #[test]
+    fn test_add_file_to_sync_target() {
+        let mut project = XcodeProject::open(&format!("{}/007-xcode16.pbxproj", FIXTURES_DIR)).unwrap();
+        let views = "3E7D82792C3892F2006B36EB";
+        let app = "3E7D82632C3892C4006B36EB";
+        let ui_tests = "3E1468042C39020A00BA1C9C";
+
+        // The app's existing exception set gains the path, in sorted position
+        assert_eq!(
+            project.add_file_to_sync_target(views, app, "GameSummaryView.swift"),
+            Ok(Some("3E7D827A2C3892FB006B36EB".to_string()))
+        );
+        let set = project.get_object("3E7D827A2C3892FB006B36EB").unwrap();
+        let paths: Vec<&str> = set
+            .get_array("membershipExceptions")
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(
+            paths[..3],
+            ["GameListView.swift", "GameListViewCell.swift", "GameSummaryView.swift"]
+        );
+
+        // Another target gets its own exception set on the group
+        let set_uuid = project
+            .add_file_to_sync_target(views, ui_tests, "GameView.swift")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            project.add_file_to_sync_target(views, ui_tests, "GameView.swift"),
+            Ok(Some(set_uuid.clone()))
+        );
+        let exceptions = project.get_object(views).unwrap().get_array("exceptions").unwrap();
+        assert_eq!(exceptions.len(), 2);
+        assert_eq!(exceptions[1].as_str(), Some(set_uuid.as_str()));
+
+        let output = project.to_pbxproj();
+        let expected = format!(
+            "\t\t{set_uuid} /* PBXFileSystemSynchronizedBuildFileExceptionSet */ = {{\n\t\t\tisa = PBXFileSystemSynchronizedBuildFileExceptionSet;\n\t\t\tmembershipExceptions = (\n\t\t\t\tGameView.swift,\n\t\t\t);\n\t\t\ttarget = {ui_tests} /* ScoreTallyUITests */;\n\t\t}};\n"
+        );
+        assert!(output.contains(&expected));
+        let reparsed = XcodeProject::from_plist(&output).unwrap();
+        assert_eq!(reparsed.to_pbxproj(), output);
+
+        // Bad UUIDs are errors, and leave the project alone
+        let objects_before = project.objects().count();
+        assert!(project.add_file_to_sync_target(app, app, "File.swift").is_err());
+        assert!(project
+            .add_file_to_sync_target("nonexistent-uuid", app, "File.swift")
+            .is_err());
+        assert!(project
+            .add_file_to_sync_target(views, "nonexistent-uuid", "File.swift")
+            .is_err());
+        assert!(project.add_file_to_sync_target(views, views, "File.swift").is_err());
+        assert_eq!(project.objects().count(), objects_before);
+    }
+
+    #[test]
+    fn test_add_file_to_sync_target_already_attached() {
+        let mut project = XcodeProject::open(&format!("{}/007-xcode16.pbxproj", FIXTURES_DIR)).unwrap();
+        let views = "3E7D82792C3892F2006B36EB";
+        let app = "3E7D82632C3892C4006B36EB";
+        let ui_tests = "3E1468042C39020A00BA1C9C";
+        for target in [app, ui_tests] {
+            project.get_object_mut(target).unwrap().set_sorted(
+                "fileSystemSynchronizedGroups",
+                PlistValue::Array(vec![PlistValue::String(Cow::Owned(views.to_string()))]),
+            );
+        }
+        let exception_paths = |project: &XcodeProject| -> Vec<String> {
+            project
+                .get_object("3E7D827A2C3892FB006B36EB")
+                .unwrap()
+                .get_array("membershipExceptions")
+                .unwrap()
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        };
+
+        // An excluded file is brought back by dropping it from the target's exception set
+        assert_eq!(
+            project.add_file_to_sync_target(views, app, "GameView.swift"),
+            Ok(Some("3E7D827A2C3892FB006B36EB".to_string()))
+        );
+        let paths = exception_paths(&project);
+        assert_eq!(paths.len(), 4);
+        assert!(!paths.contains(&"GameView.swift".to_string()));
+
+        // Files the target already builds need no exception set
+        let objects_before = project.objects().count();
+        assert_eq!(project.add_file_to_sync_target(views, app, "NewView.swift"), Ok(None));
+        assert_eq!(
+            project.add_file_to_sync_target(views, ui_tests, "GameView.swift"),
+            Ok(None)
+        );
+        assert_eq!(project.objects().count(), objects_before);
+        assert_eq!(
+            project
+                .get_object(views)
+                .unwrap()
+                .get_array("exceptions")
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(exception_paths(&project).len(), 4);
+    }
+
+    #[test]
+    fn test_remove_build_setting_all_targets() {
+        let mut project = XcodeProject::open(&format!("{}/watch.pbxproj", FIXTURES_DIR)).unwrap();
+        let targets = project.target_uuids();
+        assert!(targets.len() > 1);
+        for target in &targets {
+            assert!(project.set_build_setting(target, "ENABLE_BITCODE", PlistValue::String(Cow::Borrowed("NO"))));
+        }
+        let seeded = project
+            .objects_by_isa("XCBuildConfiguration")
+            .iter()
+            .filter(|c| {
+                c.get_object("buildSettings")
+                    .is_some_and(|s| s.iter().any(|(k, _)| k == "ENABLE_BITCODE"))
+            })
+            .count();
+
+        assert_eq!(project.remove_build_setting_all_targets("ENABLE_BITCODE"), seeded);
+        assert_eq!(seeded, targets.len() * 2);
+        for target in &targets {
+            assert_eq!(project.get_build_setting(target, "ENABLE_BITCODE"), None);
+        }
+        assert_eq!(project.remove_build_setting_all_targets("ENABLE_BITCODE"), 0);
+    }
+
+    #[test]
+    fn test_project_build_setting() {
+        let mut project = XcodeProject::open(&format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        assert_eq!(project.get_project_build_setting("SWIFT_VERSION"), None);
+
+        assert!(project.set_project_build_setting("SWIFT_VERSION", PlistValue::String(Cow::Borrowed("6.0"))));
+        assert_eq!(
+            project.get_project_build_setting("SWIFT_VERSION"),
+            Some(PlistValue::String(Cow::Borrowed("6.0")))
+        );
+        let list = project.build_configuration_list_uuid().unwrap();
+        for config in project
+            .get_object(&list)
+            .unwrap()
+            .get_array("buildConfigurations")
+            .unwrap()
+        {
+            let settings = project
+                .get_object(config.as_str().unwrap())
+                .unwrap()
+                .get_object("buildSettings")
+                .unwrap();
+            assert!(settings
+                .iter()
+                .any(|(k, v)| k == "SWIFT_VERSION" && v.as_str() == Some("6.0")));
+        }
+
+        // Targets that set their own value keep overriding the project's
+        let target = project.target_uuids()[0].clone();
+        let resolved = project.resolved_build_settings(&target, "Debug").unwrap();
+        assert_eq!(resolved.get("SWIFT_VERSION").map(String::as_str), Some("5.0"));
+    }
+
+    #[test]
+    fn test_retain_targets() {
+        let mut project = XcodeProject::open(&format!("{}/project-multitarget.pbxproj", FIXTURES_DIR)).unwrap();
+        let app = "13B07F861A680F5B00A75B9A".to_string();
+        let share_extension = "6ADAD6E126493A420001F56E";
+        let extension_phases: Vec<String> = project
+            .get_object(share_extension)
+            .unwrap()
+            .get_array("buildPhases")
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+        let app_phases = project
+            .get_object(&app)
+            .unwrap()
+            .get_array("buildPhases")
+            .unwrap()
+            .clone();
+
+        assert_eq!(project.retain_targets(&[app.clone()]), 1);
+        assert_eq!(project.target_uuids(), vec![app.clone()]);
+        assert!(project.get_dependencies(&app).is_empty());
+        assert!(extension_phases.iter().all(|uuid| !project.contains(uuid)));
+        assert!(project.find_orphaned_references().is_empty());
+        assert!(project.find_cycles().is_empty());
+
+        // The kept target's phases, configurations and files are intact
+        for phase in app_phases.iter().filter_map(|v| v.as_str()) {
+            assert!(project.contains(phase));
+        }
+        assert!(project.get_build_setting(&app, "PRODUCT_NAME").is_some());
+        assert!(project
+            .get_object(&app)
+            .unwrap()
+            .get_str("productReference")
+            .is_some_and(|p| project.contains(p)));
+
+        let output = project.to_pbxproj();
+        assert!(!output.contains(share_extension));
+        assert_eq!(XcodeProject::from_plist(&output).unwrap().to_pbxproj(), output);
+        assert_eq!(project.retain_targets(&[app]), 0);
+    }
+
+    #[test]
+    fn test_retain_targets_with_non_hex_ids() {
+        for (fixture, keep) in [
+            ("swift-protobuf.pbxproj", "______Target_Protobuf"),
+            ("009-expo-app-clip.pbxproj", "XX42A75F8F031FED491C16XX"),
+        ] {
+            let mut project = XcodeProject::open(&format!("{}/{}", FIXTURES_DIR, fixture)).unwrap();
+            let phases: Vec<String> = project
+                .get_object(keep)
+                .unwrap()
+                .references("buildPhases")
+                .into_iter()
+                .map(String::from)
+                .collect();
+
+            assert!(project.retain_targets(&[keep.to_string()]) > 0, "{fixture}");
+            assert_eq!(project.target_uuids(), vec![keep.to_string()], "{fixture}");
+            assert!(project.find_orphaned_references().is_empty(), "{fixture}");
+            assert!(phases.iter().all(|uuid| project.contains(uuid)), "{fixture}");
+            assert!(project.get_build_setting(keep, "PRODUCT_NAME").is_some(), "{fixture}");
+        }
+    }
+
+    #[test]
+    fn test_to_json_sorted_ignores_key_order() {
+        let project = XcodeProject::open(&format!("{}/AFNetworking.pbxproj", FIXTURES_DIR)).unwrap();
+        let mut reordered = project.clone();
+        for (_, obj) in reordered.objects_mut() {
+            obj.props.reverse();
+            if let Some(PlistValue::Object(settings)) = obj.props.get_mut("buildSettings") {
+                settings.reverse();
+            }
+        }
+        assert_ne!(
+            serde_json::to_string(&project.to_json().unwrap()).unwrap(),
+            serde_json::to_string(&reordered.to_json().unwrap()).unwrap()
+        );
+
+        let sorted = project.to_json_sorted().unwrap();
+        assert_eq!(
+            serde_json::to_string(&sorted).unwrap(),
+            serde_json::to_string(&reordered.to_json_sorted().unwrap()).unwrap()
+        );
+        let objects = sorted["objects"].as_object().unwrap();
+        assert!(objects.keys().eq(project.objects().map(|(uuid, _)| uuid)));
+        let root = &objects[project.root_object_uuid.as_str()];
+        let keys: Vec<&String> = root.as_object().unwrap().keys().collect();
+        assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Only the top-level `objects` map keeps its order; nested maps named `objects` are sorted.
+        let mut nested = project.clone();
+        let root_uuid = nested.root_object_uuid.clone();
+        nested.get_object_mut(&root_uuid).unwrap().set(
+            "objects",
+            PlistValue::Object(vec![
+                (Cow::Borrowed("b"), PlistValue::String(Cow::Borrowed("2"))),
+                (Cow::Borrowed("a"), PlistValue::String(Cow::Borrowed("1"))),
+            ]),
+        );
+        let sorted = nested.to_json_sorted().unwrap();
+        let nested_keys: Vec<&String> = sorted["objects"][root_uuid.as_str()]["objects"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect();
+        assert_eq!(nested_keys, ["a", "b"]);
+    }
+
+    #[test]
+    fn test_insert_child_at() {
+        let mut project = XcodeProject::open(&format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let original = project.get_group_children(&main_group);
+        assert!(original.len() >= 2);
+
+        let first = project.add_group(&main_group, "First").unwrap();
+        assert!(project.insert_child_at(&main_group, &first, 0));
+        let middle = project.add_file(&main_group, "Middle.swift").unwrap();
+        assert!(project.insert_child_at(&main_group, &middle, 2));
+        let last = project.add_group(&main_group, "Last").unwrap();
+        assert!(project.insert_child_at(&main_group, &last, 1000));
+
+        let children = project.get_group_children(&main_group);
+        assert_eq!(children.len(), original.len() + 3);
+        assert_eq!(children[0], first);
+        assert_eq!(children[1], original[0]);
+        assert_eq!(children[2], middle);
+        assert_eq!(children[3], original[1]);
+        assert_eq!(children.last(), Some(&last));
+
+        let target = project.target_uuids()[0].clone();
+        assert!(!project.insert_child_at(&target, &first, 0));
+    }
+
+    #[test]
+    fn test_last_upgrade_and_swift_update_checks() {
+        let mut project = XcodeProject::open(&format!("{}/project.pbxproj", FIXTURES_DIR)).unwrap();
+        assert_eq!(project.last_upgrade_check(), Some("1130".to_string()));
+        assert_eq!(project.last_swift_update_check(), None);
+
+        assert!(project.set_last_upgrade_check("1630"));
+        assert!(project.set_last_swift_update_check("1630"));
+        assert_eq!(project.last_upgrade_check(), Some("1630".to_string()));
+        assert_eq!(project.last_swift_update_check(), Some("1630".to_string()));
+
+        let output = project.to_pbxproj();
+        assert!(output.contains(
+            "\t\t\t\tLastSwiftUpdateCheck = 1630;\n\t\t\t\tLastUpgradeCheck = 1630;\n\t\t\t\tTargetAttributes = {"
+        ));
+
+        let created = XcodeProject::new_empty("App", None);
+        assert_eq!(created.last_upgrade_check().as_deref(), Some(LAST_UPGRADE_CHECK));
+        assert_eq!(created.last_swift_update_check().as_deref(), Some(LAST_UPGRADE_CHECK));
+    }
+
+    #[test]
+    fn test_add_folder_reference() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let target = project.native_targets()[0].uuid.clone();
+
+        let folder = project
+            .add_folder_reference(&main_group, "Assets/Levels", Some(&target))
+            .unwrap();
+        let file_ref = project.get_object(&folder).unwrap();
+        assert_eq!(file_ref.isa, "PBXFileReference");
+        assert_eq!(file_ref.get_str("lastKnownFileType"), Some("folder"));
+        assert_eq!(file_ref.get_str("name"), Some("Levels"));
+        assert_eq!(file_ref.get_str("sourceTree"), Some("<group>"));
+        assert!(!file_ref.props.contains_key("fileEncoding"));
+        assert!(project.get_group_children(&main_group).contains(&folder));
+
+        let resources = project
+            .find_build_phase(&target, "PBXResourcesBuildPhase")
+            .unwrap()
+            .uuid
+            .clone();
+        let in_resources = project
+            .get_object(&resources)
+            .unwrap()
+            .get_array("files")
+            .unwrap()
+            .iter()
+            .any(|f| {
+                f.as_str()
+                    .and_then(|uuid| project.get_object(uuid))
+                    .is_some_and(|bf| bf.get_str("fileRef") == Some(folder.as_str()))
+            });
+        assert!(in_resources);
+
+        let plain = project.add_folder_reference(&main_group, "Docs", None).unwrap();
+        assert!(project.get_object(&plain).unwrap().get_str("name").is_none());
+        assert!(project
+            .add_folder_reference("000000000000000000000000", "Docs", None)
+            .is_none());
+
+        // A non-target is refused before the reference is created
+        let objects_before = project.objects().count();
+        assert!(project
+            .add_folder_reference(&main_group, "Assets", Some(&main_group))
+            .is_none());
+        assert_eq!(project.objects().count(), objects_before);
+    }
+
+    #[test]
+    fn test_add_asset_catalog() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let target = project.native_targets()[0].uuid.clone();
+
+        let catalog = project
+            .add_asset_catalog(&target, &main_group, "App/Media.xcassets")
+            .unwrap();
+        let file_ref = project.get_object(&catalog).unwrap();
+        assert_eq!(file_ref.get_str("lastKnownFileType"), Some("folder.assetcatalog"));
+        assert!(project.get_group_children(&main_group).contains(&catalog));
+
+        let resources = project.find_build_phase(&target, "PBXResourcesBuildPhase").unwrap();
+        let files = resources.get_array("files").unwrap();
+        assert!(files.iter().any(|f| {
+            f.as_str()
+                .and_then(|uuid| project.get_object(uuid))
+                .is_some_and(|bf| bf.get_str("fileRef") == Some(catalog.as_str()))
+        }));
+        assert!(project
+            .add_asset_catalog("000000000000000000000000", &main_group, "A.xcassets")
+            .is_none());
+        let objects_before = project.objects().count();
+        assert!(project
+            .add_asset_catalog(&main_group, &main_group, "A.xcassets")
+            .is_none());
+        assert_eq!(project.objects().count(), objects_before);
+    }
+
+    #[test]
+    fn test_find_orphaned_references_in_matches_full_scan() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let target = project.native_targets()[0].uuid.clone();
+        let phase = project
+            .find_build_phase(&target, "PBXSourcesBuildPhase")
+            .unwrap()
+            .uuid
+            .clone();
+
+        let missing = PlistValue::String(Cow::Owned("DEADBEEF0000000000000001".to_string()));
+        for (uuid, key) in [(&main_group, "children"), (&phase, "files")] {
+            if let Some(PlistValue::Array(items)) = project.get_object_mut(uuid).unwrap().props.get_mut(key) {
+                items.push(missing.clone());
+            }
+        }
+
+        let key = |o: &OrphanedReference| (o.referrer_uuid.clone(), o.property.clone(), o.orphan_uuid.clone());
+        let mut full: Vec<_> = project.find_orphaned_references().iter().map(key).collect();
+        let mut scoped: Vec<_> = project
+            .find_orphaned_references_in(&[main_group.clone(), phase.clone(), "NOT-AN-OBJECT".to_string()])
+            .iter()
+            .map(key)
+            .collect();
+        full.sort();
+        scoped.sort();
+        assert_eq!(scoped.len(), 2);
+        assert_eq!(scoped, full);
+
+        assert!(project.find_orphaned_references_in(&[target]).is_empty());
+    }
+
+    #[test]
+    fn test_orphan_suggested_action() {
+        let orphan = |isa: &str, property: &str, in_array: bool| OrphanedReference {
+            referrer_uuid: "AAAA00000000000000000001".to_string(),
+            referrer_isa: isa.to_string(),
+            property: property.to_string(),
+            orphan_uuid: "DEADBEEF0000000000000001".to_string(),
+            in_array,
+        };
+        assert_eq!(
+            orphan("PBXGroup", "children", true).suggested_action(),
+            RepairAction::RemoveFromArray
+        );
+        assert_eq!(
+            orphan("PBXSourcesBuildPhase", "files", true).suggested_action(),
+            RepairAction::RemoveFromArray
+        );
+        assert_eq!(
+            orphan("XCBuildConfiguration", "baseConfigurationReference", false).suggested_action(),
+            RepairAction::ClearScalar
+        );
+        assert_eq!(
+            orphan("PBXNativeTarget", "productReference", false).suggested_action(),
+            RepairAction::ClearScalar
+        );
+        assert_eq!(
+            orphan("PBXBuildFile", "fileRef", false).suggested_action(),
+            RepairAction::RemoveReferrer
+        );
+        assert_eq!(
+            orphan("PBXTargetDependency", "target", false).suggested_action(),
+            RepairAction::RemoveReferrer
+        );
+
+        let content = fs::read_to_string(Path::new(FIXTURES_DIR).join("malformed.pbxproj")).unwrap();
+        let mut project = XcodeProject::from_plist(&content).unwrap();
+        let known = project
+            .find_orphaned_references()
+            .into_iter()
+            .find(|o| o.orphan_uuid == "3E1C2299F05049539341855D")
+            .unwrap();
+        assert!(known.in_array);
+        assert_eq!(known.suggested_action(), RepairAction::RemoveFromArray);
+
+        // Registered keys are classified by the shape of their value, not their name.
+        let file_ref = project.find_objects_by_isa("PBXFileReference")[0].clone();
+        let obj = project.get_object_mut(&file_ref).unwrap();
+        obj.set_str("companionFile", "DEADBEEF0000000000000002");
+        obj.set(
+            "companionFiles",
+            PlistValue::Array(vec![PlistValue::String(Cow::Borrowed("DEADBEEF0000000000000003"))]),
+        );
+        project.register_reference_keys("PBXFileReference", &["companionFile", "companionFiles"]);
+        let action = |orphan_uuid: &str| {
+            project
+                .find_orphaned_references_in(&[file_ref.clone()])
+                .into_iter()
+                .find(|o| o.orphan_uuid == orphan_uuid)
+                .map(|o| o.suggested_action())
+        };
+        assert_eq!(action("DEADBEEF0000000000000002"), Some(RepairAction::ClearScalar));
+        assert_eq!(action("DEADBEEF0000000000000003"), Some(RepairAction::RemoveFromArray));
+    }
+
+    #[test]
+    fn test_register_reference_keys() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let file_refs = project.find_objects_by_isa("PBXFileReference");
+        let (source, header) = (file_refs[0].clone(), file_refs[1].clone());
+        project
+            .get_object_mut(&source)
+            .unwrap()
+            .set_str("companionFile", &header);
+
+        assert!(project.collect_references(&source).is_empty());
+        assert!(project.get_referrers(&header).iter().all(|obj| obj.uuid != source));
+
+        project.register_reference_keys("PBXFileReference", &["companionFile", "companionFile"]);
+        assert_eq!(project.collect_references(&source), HashSet::from([header.clone()]));
+        assert!(project.get_referrers(&header).iter().any(|obj| obj.uuid == source));
+        let source_obj = project.get_object(&source).unwrap();
+        assert_eq!(
+            project
+                .reference_keys_for(source_obj)
+                .iter()
+                .filter(|k| **k == "companionFile")
+                .count(),
+            1
+        );
+
+        project.remove_object(&header);
+        assert_eq!(project.get_object(&source).unwrap().get_str("companionFile"), Some(""));
+    }
+
+    #[test]
+    fn test_reference_getters_accept_non_hex_ids() {
+        let content = fs::read_to_string(Path::new(FIXTURES_DIR).join("009-expo-app-clip.pbxproj")).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+        let clip = project.get_object("XX42A75F8F031FED491C16XX").unwrap();
+
+        assert_eq!(
+            clip.reference("buildConfigurationList"),
+            Some("XX32BFEE75D9DD55518519XX")
+        );
+        let phases = clip.references("buildPhases");
+        assert_eq!(phases.len(), 8);
+        assert!(phases.contains(&"XXE45443DC38DCFE1DB622XX"));
+        assert!(phases.iter().all(|uuid| project.contains(uuid)));
+    }
+
+    #[test]
+    fn test_normalize_paths() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let windows = project.add_file(&main_group, "Sources\\App\\main.swift").unwrap();
+        project
+            .get_object_mut(&windows)
+            .unwrap()
+            .set_str("name", "Sources\\App\\main.swift");
+        let group = project.add_group(&main_group, "Shared").unwrap();
+        project
+            .get_object_mut(&group)
+            .unwrap()
+            .set_str("path", "Vendor\\Shared");
+        let escaped = project.add_file(&main_group, "My\\ File\\\\.txt").unwrap();
+
+        assert_eq!(project.normalize_paths(), 2);
+        let file = project.get_object(&windows).unwrap();
+        assert_eq!(file.get_str("path"), Some("Sources/App/main.swift"));
+        assert_eq!(file.get_str("name"), Some("Sources/App/main.swift"));
+        let group = project.get_object(&group).unwrap();
+        assert_eq!(group.get_str("path"), Some("Vendor/Shared"));
+        assert_eq!(group.get_str("name"), Some("Shared"));
+        assert_eq!(
+            project.get_object(&escaped).unwrap().get_str("path"),
+            Some("My\\ File\\\\.txt")
+        );
+
+        assert_eq!(project.normalize_paths(), 0);
+    }
+
+    #[test]
+    fn test_product_path_and_name() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let target = project
+            .find_target_by_product_type("com.apple.product-type.application")
+            .unwrap()
+            .uuid
+            .clone();
+
+        assert_eq!(project.product_path(&target).as_deref(), Some("testproject.app"));
+        assert_eq!(project.product_name(&target).as_deref(), Some("testproject"));
+
+        project.set_build_setting(
+            &target,
+            "PRODUCT_NAME",
+            PlistValue::String(Cow::Borrowed("$(TARGET_NAME)")),
+        );
+        let target_name = project.get_target_name(&target).unwrap();
+        assert_eq!(project.product_name(&target), Some(target_name));
+
+        assert_eq!(project.product_path("000000000000000000000000"), None);
+        assert_eq!(project.product_name("000000000000000000000000"), None);
+    }
+
+    #[test]
+    fn test_set_default_configuration() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let target = project.native_targets()[0].uuid.clone();
+        let list = project.target_config_list(&target).unwrap();
+        assert_eq!(project.default_configuration_name(&list).as_deref(), Some("Release"));
+
+        assert!(project.set_default_configuration(&list, "Debug"));
+        assert_eq!(project.default_configuration_name(&list).as_deref(), Some("Debug"));
+        assert_eq!(project.default_config_name(&target).as_deref(), Some("Debug"));
+        let config = project.get_default_configuration(&list).unwrap();
+        assert_eq!(config.get_str("name"), Some("Debug"));
+
+        assert!(!project.set_default_configuration(&list, "Staging"));
+        assert_eq!(project.default_configuration_name(&list).as_deref(), Some("Debug"));
+        assert!(!project.set_default_configuration("000000000000000000000000", "Debug"));
+    }
+
+    #[test]
+    fn test_rename_file() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let app_delegate = "13B07FB01A68108700A75B9A";
+        let build_file = "13B07FBC1A68108700A75B9A";
+
+        assert!(project.rename_file(app_delegate, "testproject/AppDelegate.mm"));
+        let file_ref = project.get_object(app_delegate).unwrap();
+        assert_eq!(file_ref.get_str("path"), Some("testproject/AppDelegate.mm"));
+        assert_eq!(file_ref.get_str("name"), Some("AppDelegate.mm"));
+        assert_eq!(file_ref.get_str("lastKnownFileType"), Some("sourcecode.cpp.objcpp"));
+        assert_eq!(
+            project.get_object(build_file).unwrap().get_str("fileRef"),
+            Some(app_delegate)
+        );
+
+        let main_group = project.main_group_uuid().unwrap();
+        let unnamed = project.add_file(&main_group, "Notes.txt").unwrap();
+        assert!(project.rename_file(&unnamed, "README.md"));
+        assert!(project.get_object(&unnamed).unwrap().get_str("name").is_none());
+
+        assert!(!project.rename_file(&main_group, "Other"));
+        assert!(!project.rename_file("000000000000000000000000", "a.m"));
+    }
+
+    #[test]
+    fn test_move_group() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let features = project.add_group(&main_group, "Features").unwrap();
+        let login = project.add_group(&features, "Login").unwrap();
+        let shared = project.add_group(&main_group, "Shared").unwrap();
+
+        assert!(project.move_group(&login, &features, &shared));
+        assert!(project.get_group_children(&features).is_empty());
+        assert_eq!(project.get_group_children(&shared), vec![login.clone()]);
+
+        // Moving a group under itself or its own descendant would create a cycle.
+        assert!(!project.move_group(&shared, &main_group, &login));
+        assert!(!project.move_group(&shared, &main_group, &shared));
+        assert!(project.get_group_children(&main_group).contains(&shared));
+        assert_eq!(project.get_group_children(&shared), vec![login.clone()]);
+
+        // The source parent must actually list the group.
+        assert!(!project.move_group(&login, &features, &main_group));
+        assert!(project.find_cycles().is_empty());
+    }
+
+    #[test]
+    fn test_set_source_tree() {
+        let path = Path::new(FIXTURES_DIR).join("project.pbxproj");
+        let mut project = XcodeProject::open(path.to_str().unwrap()).unwrap();
+        let main_group = project.main_group_uuid().unwrap();
+        let file = project.add_file(&main_group, "Shared/Config.swift").unwrap();
+
+        for valid in [
+            "SOURCE_ROOT",
+            "<absolute>",
+            "BUILT_PRODUCTS_DIR",
+            "PODS_ROOT",
+            "<group>",
+        ] {
+            assert_eq!(project.set_source_tree(&file, valid), Ok(()));
+            assert_eq!(project.get_object(&file).unwrap().get_str("sourceTree"), Some(valid));
+        }
+
+        for invalid in ["group", "<Group>", "source_root", "$(SRCROOT)", "", "SDK ROOT"] {
+            let err = project.set_source_tree(&file, invalid).unwrap_err();
+            assert!(err.contains("Unknown sourceTree"), "{}", err);
+        }
+        assert_eq!(
+            project.get_object(&file).unwrap().get_str("sourceTree"),
+            Some("<group>")
+        );
+
+        assert!(project.set_source_tree(&main_group, "SOURCE_ROOT").is_ok());
+        let target = project.native_targets()[0].uuid.clone();
+        assert!(project
+            .set_source_tree(&target, "SOURCE_ROOT")
+            .unwrap_err()
+            .contains("has no sourceTree"));
+        assert!(project
+            .set_source_tree("000000000000000000000000", "SOURCE_ROOT")
+            .is_err());
+    }
 }
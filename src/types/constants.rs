@@ -30,9 +30,12 @@ pub static FILE_TYPES_BY_EXTENSION: LazyLock<HashMap<&'static str, &'static str>
     m.insert("jpg", "image.jpeg");
     m.insert("js", "sourcecode.javascript");
     m.insert("json", "text.json");
+    m.insert("kext", "wrapper.kernel-extension");
     m.insert("m", "sourcecode.c.objc");
     m.insert("markdown", "net.daringfireball.markdown");
     m.insert("md", "net.daringfireball.markdown");
+    m.insert("mlmodel", "file.mlmodel");
+    m.insert("mlpackage", "folder.mlpackage");
     m.insert("mm", "sourcecode.cpp.objcpp");
     m.insert("modulemap", "sourcecode.module");
     m.insert("mp3", "audio.mp3");
@@ -45,6 +48,7 @@ pub static FILE_TYPES_BY_EXTENSION: LazyLock<HashMap<&'static str, &'static str>
     m.insert("strings", "text.plist.strings");
     m.insert("stringsdict", "text.plist.stringsdict");
     m.insert("swift", "sourcecode.swift");
+    m.insert("systemextension", "wrapper.system-extension");
     m.insert("tbd", "sourcecode.text-based-dylib-definition");
     m.insert("ts", "sourcecode.javascript");
     m.insert("tsx", "sourcecode.javascript");
@@ -55,8 +59,10 @@ pub static FILE_TYPES_BY_EXTENSION: LazyLock<HashMap<&'static str, &'static str>
     m.insert("xcdatamodel", "wrapper.xcdatamodel");
     m.insert("xcdatamodeld", "wrapper.xcdatamodeld");
     m.insert("xcframework", "wrapper.xcframework");
+    m.insert("xctest", "wrapper.cfbundle");
     m.insert("xib", "file.xib");
     m.insert("xml", "text.xml");
+    m.insert("xpc", "wrapper.xpc-service");
     m.insert("yaml", "text.yaml");
     m.insert("yml", "text.yaml");
     m.insert("zip", "archive.zip");
@@ -79,6 +85,20 @@ pub static PRODUCT_UTI_EXTENSIONS: LazyLock<HashMap<&'static str, &'static str>>
     m.insert("com.apple.product-type.application.watchapp", "app");
     m.insert("com.apple.product-type.application.watchapp2", "app");
     m.insert("com.apple.product-type.watchkit-extension", "appex");
+    m.insert("com.apple.product-type.watchkit2-extension", "appex");
+    m.insert("com.apple.product-type.application.watchapp2-container", "app");
+    m.insert("com.apple.product-type.application.messages", "app");
+    m.insert("com.apple.product-type.app-extension.messages", "appex");
+    m.insert("com.apple.product-type.app-extension.messages-sticker-pack", "appex");
+    m.insert("com.apple.product-type.extensionkit-extension", "appex");
+    m.insert("com.apple.product-type.tv-app-extension", "appex");
+    m.insert("com.apple.product-type.xcode-extension", "appex");
+    m.insert("com.apple.product-type.framework.static", "framework");
+    m.insert("com.apple.product-type.bundle.unit-test", "xctest");
+    m.insert("com.apple.product-type.bundle.ui-testing", "xctest");
+    m.insert("com.apple.product-type.xpc-service", "xpc");
+    m.insert("com.apple.product-type.system-extension", "systemextension");
+    m.insert("com.apple.product-type.kernel-extension", "kext");
     m
 });
 
@@ -93,6 +113,55 @@ pub static SOURCETREE_BY_FILETYPE: LazyLock<HashMap<&'static str, &'static str>>
     m
 });
 
+/// Which kind of build phase (if any) a file type belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCategory {
+    /// Compiled by a Sources build phase.
+    Source,
+    /// C/C++/Objective-C headers.
+    Header,
+    /// Linked by a Frameworks build phase.
+    Framework,
+    /// Copied by a Resources build phase.
+    Resource,
+    /// Build products, configuration files and anything else that isn't added to a phase.
+    Other,
+}
+
+/// Classify a `lastKnownFileType` UTI by the build phase it belongs in.
+pub fn file_category(file_type: &str) -> FileCategory {
+    match file_type {
+        "sourcecode.c.c"
+        | "sourcecode.c.objc"
+        | "sourcecode.cpp.cpp"
+        | "sourcecode.cpp.objcpp"
+        | "sourcecode.swift"
+        | "sourcecode.asm"
+        | "sourcecode.metal"
+        | "sourcecode.dtrace"
+        | "file.intentdefinition"
+        | "file.mlmodel"
+        | "wrapper.xcdatamodel"
+        | "wrapper.xcdatamodeld" => FileCategory::Source,
+        "sourcecode.c.h" | "sourcecode.cpp.h" => FileCategory::Header,
+        "wrapper.framework"
+        | "wrapper.xcframework"
+        | "compiled.mach-o.dylib"
+        | "archive.ar"
+        | "sourcecode.text-based-dylib-definition" => FileCategory::Framework,
+        ""
+        | "wrapper.application"
+        | "wrapper.app-extension"
+        | "wrapper.plug-in"
+        | "wrapper.cfbundle"
+        | "wrapper.pb-project"
+        | "sourcecode.module"
+        | "text.xcconfig"
+        | "text.plist.entitlements" => FileCategory::Other,
+        _ => FileCategory::Resource,
+    }
+}
+
 // SDK version constants (Xcode 26.2 / Dec 2025)
 pub const LAST_KNOWN_IOS_SDK: &str = "26.2";
 pub const LAST_KNOWN_OSX_SDK: &str = "26.2";
@@ -103,6 +172,55 @@ pub const LAST_KNOWN_ARCHIVE_VERSION: i64 = 1;
 pub const LAST_KNOWN_OBJECT_VERSION: i64 = 77;
 pub const DEFAULT_OBJECT_VERSION: i64 = 46;
 pub const LAST_UPGRADE_CHECK: &str = "2600";
+/// First `objectVersion` written by Xcode 16 (file-system synchronized groups). From this
+/// version on, `PBXProject` records the chosen format in `preferredProjectObjectVersion`.
+pub const XCODE_16_OBJECT_VERSION: i64 = 70;
+/// First `objectVersion` whose `PBXProject` sets `minimizedProjectReferenceProxies`.
+pub const MINIMIZED_PROXIES_OBJECT_VERSION: i64 = 77;
+
+/// Oldest deployment target Xcode accepts for each platform's deployment-target setting.
+pub static MIN_DEPLOYMENT_TARGETS: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+    let mut m = HashMap::new();
+    m.insert("IPHONEOS_DEPLOYMENT_TARGET", "12.0");
+    m.insert("MACOSX_DEPLOYMENT_TARGET", "10.13");
+    m.insert("TVOS_DEPLOYMENT_TARGET", "12.0");
+    m.insert("WATCHOS_DEPLOYMENT_TARGET", "4.0");
+    m.insert("XROS_DEPLOYMENT_TARGET", "1.0");
+    m
+});
+
+/// Build settings that only accept `YES` or `NO`, beyond those in [`ProjectDefaultBuildSettings`].
+pub const BOOLEAN_BUILD_SETTINGS: &[&str] = &[
+    "ASSETCATALOG_COMPILER_GENERATE_SWIFT_ASSET_SYMBOL_EXTENSIONS",
+    "CODE_SIGN_INJECT_BASE_ENTITLEMENTS",
+    "COPY_PHASE_STRIP",
+    "DEAD_CODE_STRIPPING",
+    "ENABLE_BITCODE",
+    "ENABLE_HARDENED_RUNTIME",
+    "ENABLE_PREVIEWS",
+    "ENABLE_USER_SCRIPT_SANDBOXING",
+    "GENERATE_INFOPLIST_FILE",
+    "SKIP_INSTALL",
+    "SWIFT_EMIT_LOC_STRINGS",
+];
+
+/// `sourceTree` values Xcode itself writes. Any other value must name a build
+/// setting, such as a custom source tree from Xcode's Locations preferences.
+pub const SOURCE_TREES: &[&str] = &[
+    "<absolute>",
+    "<group>",
+    "BUILT_PRODUCTS_DIR",
+    "DEVELOPER_DIR",
+    "SDKROOT",
+    "SOURCE_ROOT",
+];
+
+/// Which configuration-specific defaults to apply on top of the shared ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigVariant {
+    Debug,
+    Release,
+}
 
 /// Default build settings for new projects.
 pub struct ProjectDefaultBuildSettings;
@@ -166,6 +284,16 @@ impl ProjectDefaultBuildSettings {
         m
     }
 
+    /// Shared defaults merged with the ones specific to `variant`.
+    pub fn for_variant(variant: ConfigVariant) -> HashMap<&'static str, &'static str> {
+        let mut m = Self::all();
+        match variant {
+            ConfigVariant::Debug => m.extend(Self::debug()),
+            ConfigVariant::Release => m.extend(Self::release()),
+        }
+        m
+    }
+
     pub fn release() -> HashMap<&'static str, &'static str> {
         let mut m = HashMap::new();
         m.insert("DEBUG_INFORMATION_FORMAT", "dwarf-with-dsym");
@@ -175,3 +303,21 @@ impl ProjectDefaultBuildSettings {
         m
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_category() {
+        assert_eq!(file_category("sourcecode.swift"), FileCategory::Source);
+        assert_eq!(file_category("sourcecode.c.objc"), FileCategory::Source);
+        assert_eq!(file_category("sourcecode.c.h"), FileCategory::Header);
+        assert_eq!(file_category("image.png"), FileCategory::Resource);
+        assert_eq!(file_category("file.storyboard"), FileCategory::Resource);
+        assert_eq!(file_category("wrapper.framework"), FileCategory::Framework);
+        assert_eq!(file_category("text.xcconfig"), FileCategory::Other);
+        assert_eq!(file_category("file.mlmodel"), FileCategory::Source);
+        assert_eq!(file_category("folder.mlpackage"), FileCategory::Resource);
+    }
+}
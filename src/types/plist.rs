@@ -95,6 +95,57 @@ impl<'a> PlistValue<'a> {
         self.as_object()
             .and_then(|pairs| pairs.iter().find(|(k, _)| k.as_ref() == key).map(|(_, v)| v))
     }
+
+    /// Like `==`, but objects compare equal when they have the same keys and values
+    /// in any order. Arrays are still compared element by element, in order.
+    pub fn deep_eq_unordered(&self, other: &PlistValue<'_>) -> bool {
+        match (self, other) {
+            (PlistValue::Object(a), PlistValue::Object(b)) => {
+                a.len() == b.len()
+                    && a.iter().all(|(key, value)| {
+                        b.iter()
+                            .find(|(k, _)| k == key)
+                            .is_some_and(|(_, v)| value.deep_eq_unordered(v))
+                    })
+            }
+            (PlistValue::Array(a), PlistValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.deep_eq_unordered(y))
+            }
+            (PlistValue::String(a), PlistValue::String(b)) => a == b,
+            (PlistValue::Integer(a), PlistValue::Integer(b)) => a == b,
+            (PlistValue::Float(a), PlistValue::Float(b)) => a == b,
+            (PlistValue::Data(a), PlistValue::Data(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Visit this value and everything nested in it, depth-first: each node before its
+    /// children, object values in key order. Keys themselves aren't visited.
+    pub fn walk<F: FnMut(&PlistValue<'a>)>(&self, mut f: F) {
+        fn visit<'a, F: FnMut(&PlistValue<'a>)>(value: &PlistValue<'a>, f: &mut F) {
+            f(value);
+            match value {
+                PlistValue::Object(pairs) => pairs.iter().for_each(|(_, v)| visit(v, f)),
+                PlistValue::Array(items) => items.iter().for_each(|v| visit(v, f)),
+                _ => {}
+            }
+        }
+        visit(self, &mut f);
+    }
+
+    /// Like [`walk`](Self::walk), but with mutable access. A node's children are
+    /// visited after `f` has run on it, so replacements made by `f` are walked too.
+    pub fn walk_mut<F: FnMut(&mut PlistValue<'a>)>(&mut self, mut f: F) {
+        fn visit<'a, F: FnMut(&mut PlistValue<'a>)>(value: &mut PlistValue<'a>, f: &mut F) {
+            f(value);
+            match value {
+                PlistValue::Object(pairs) => pairs.iter_mut().for_each(|(_, v)| visit(v, f)),
+                PlistValue::Array(items) => items.iter_mut().for_each(|v| visit(v, f)),
+                _ => {}
+            }
+        }
+        visit(self, &mut f);
+    }
 }
 
 /// Serialize PlistValue to JSON.
@@ -270,4 +321,60 @@ mod tests {
         let back: PlistValue<'static> = serde_json::from_str(&json).unwrap();
         assert_eq!(val, back);
     }
+
+    #[test]
+    fn test_deep_eq_unordered() {
+        let a = crate::parser::parse("{ isa = PBXGroup; children = ( A, B ); settings = { x = 1; y = 2; }; }").unwrap();
+        let b = crate::parser::parse("{ settings = { y = 2; x = 1; }; children = ( A, B ); isa = PBXGroup; }").unwrap();
+        assert_ne!(a, b);
+        assert!(a.deep_eq_unordered(&b));
+        assert!(b.deep_eq_unordered(&a));
+
+        let reordered_array =
+            crate::parser::parse("{ isa = PBXGroup; children = ( B, A ); settings = { x = 1; y = 2; }; }").unwrap();
+        assert!(!a.deep_eq_unordered(&reordered_array));
+        let changed =
+            crate::parser::parse("{ isa = PBXGroup; children = ( A, B ); settings = { x = 1; y = 3; }; }").unwrap();
+        assert!(!a.deep_eq_unordered(&changed));
+        let extra_key =
+            crate::parser::parse("{ isa = PBXGroup; children = ( A, B ); settings = { x = 1; y = 2; z = 3; }; }")
+                .unwrap();
+        assert!(!a.deep_eq_unordered(&extra_key));
+        assert!(!PlistValue::Integer(1).deep_eq_unordered(&PlistValue::String(Cow::Borrowed("1"))));
+    }
+
+    #[test]
+    fn test_walk_counts_variants() {
+        let value = crate::parser::parse("{ a = text; b = 1; c = 1.5; d = <0102>; e = ( x, { y = 2; } ); }").unwrap();
+        let (mut strings, mut integers, mut floats, mut data, mut objects, mut arrays) = (0, 0, 0, 0, 0, 0);
+        value.walk(|node| match node {
+            PlistValue::String(_) => strings += 1,
+            PlistValue::Integer(_) => integers += 1,
+            PlistValue::Float(_) => floats += 1,
+            PlistValue::Data(_) => data += 1,
+            PlistValue::Object(_) => objects += 1,
+            PlistValue::Array(_) => arrays += 1,
+        });
+        assert_eq!((strings, integers, floats, data, objects, arrays), (2, 2, 1, 1, 2, 1));
+    }
+
+    #[test]
+    fn test_walk_mut_transforms_values() {
+        let mut value =
+            crate::parser::parse("{ sourceTree = group; children = ( { sourceTree = sdkroot; } ); }").unwrap();
+        value.walk_mut(|node| {
+            if let PlistValue::Object(pairs) = node {
+                for (key, v) in pairs.iter_mut() {
+                    if key == "sourceTree" {
+                        if let PlistValue::String(s) = v {
+                            *s = Cow::Owned(s.to_uppercase());
+                        }
+                    }
+                }
+            }
+        });
+        assert_eq!(value.get("sourceTree").and_then(|v| v.as_str()), Some("GROUP"));
+        let child = &value.get("children").and_then(|v| v.as_array()).unwrap()[0];
+        assert_eq!(child.get("sourceTree").and_then(|v| v.as_str()), Some("SDKROOT"));
+    }
 }
@@ -1,11 +1,29 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::objects::PbxObject;
+use crate::types::plist::PlistObject;
 use crate::types::PlistValue;
 
+/// Callback that supplies the inline comment for an object, given its UUID and
+/// properties. Returning `None` falls back to the default comment.
+pub type CommentProvider = Arc<dyn Fn(&str, &PlistObject<'_>) -> Option<String> + Send + Sync>;
+
 /// Build a map of UUID → inline comment for serialization.
 ///
 /// Replicates `createReferenceList` from comments.ts.
 pub fn create_reference_list(project: &PlistValue<'_>) -> HashMap<String, String> {
+    create_reference_list_with(project, None)
+}
+
+/// Like [`create_reference_list`], but asks `provider` first for each object's comment.
+///
+/// A build file's default comment embeds its file reference's comment, so overriding
+/// a file reference also changes the `… in Sources` comments that point at it.
+pub fn create_reference_list_with(
+    project: &PlistValue<'_>,
+    provider: Option<&CommentProvider>,
+) -> HashMap<String, String> {
     let mut cache: HashMap<String, String> = HashMap::new();
 
     let objects = match project.get("objects").and_then(|o| o.as_object()) {
@@ -16,15 +34,86 @@ pub fn create_reference_list(project: &PlistValue<'_>) -> HashMap<String, String
     // Build O(1) lookup index for the objects dict (~4000 entries)
     let index: HashMap<&str, &PlistValue<'_>> =
         objects.iter().map(|(k, v)| (k.as_ref(), v)).collect();
+    let file_to_phase = build_file_phase_index(objects.iter().map(|(k, v)| (k.as_ref(), v)));
+
+    // Process all objects to build comments
+    for (id, object) in objects {
+        get_comment_for_object(id, object, &index, &file_to_phase, provider, &mut cache);
+    }
 
-    // Pre-build reverse index: build_file_uuid → (phase_isa, phase_name)
-    // This eliminates the O(n²) scan in get_build_phase_name_containing_file
+    cache
+}
+
+/// The inline comment the writer emits after `uuid`, or `None` if the object is missing
+/// or would be written without a comment.
+pub fn get_comment_for_uuid(project: &PlistValue<'_>, uuid: &str) -> Option<String> {
+    let objects = project.get("objects").and_then(|o| o.as_object())?;
+    comment_in(uuid, objects.iter().map(|(k, v)| (k.as_ref(), v)))
+}
+
+/// [`get_comment_for_uuid`] over any object source, e.g. a parsed project's [`PbxObject`]s.
+pub(crate) fn comment_in<'o, O: CommentSource + 'o>(
+    uuid: &str,
+    objects: impl Iterator<Item = (&'o str, &'o O)>,
+) -> Option<String> {
+    let objects: Vec<(&str, &O)> = objects.collect();
+    let index: HashMap<&str, &O> = objects.iter().copied().collect();
+    let object = *index.get(uuid)?;
+    let file_to_phase = build_file_phase_index(objects.into_iter());
+
+    let mut cache = HashMap::new();
+    get_comment_for_object(uuid, object, &index, &file_to_phase, None, &mut cache).filter(|c| !c.is_empty())
+}
+
+/// The property access the comment rules need, so the writer's plist objects and
+/// [`PbxObject`]s are labelled by the same code.
+pub(crate) trait CommentSource {
+    fn str_prop(&self, key: &str) -> Option<&str>;
+    fn array_prop(&self, key: &str) -> Option<&[PlistValue<'_>]>;
+    /// The comment `provider` supplies for this object, if any.
+    fn comment_override(&self, id: &str, provider: &CommentProvider) -> Option<String>;
+}
+
+impl CommentSource for PlistValue<'_> {
+    fn str_prop(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_str())
+    }
+
+    fn array_prop(&self, key: &str) -> Option<&[PlistValue<'_>]> {
+        self.get(key).and_then(|v| v.as_array()).map(|a| a.as_slice())
+    }
+
+    fn comment_override(&self, id: &str, provider: &CommentProvider) -> Option<String> {
+        provider(id, self.as_object()?)
+    }
+}
+
+impl CommentSource for PbxObject {
+    fn str_prop(&self, key: &str) -> Option<&str> {
+        self.get_str(key)
+    }
+
+    fn array_prop(&self, key: &str) -> Option<&[PlistValue<'_>]> {
+        self.get_array(key).map(|a| a.as_slice())
+    }
+
+    fn comment_override(&self, id: &str, provider: &CommentProvider) -> Option<String> {
+        provider(id, &self.to_plist())
+    }
+}
+
+/// Reverse index: build_file_uuid → (phase_isa, phase_name).
+///
+/// This eliminates the O(n²) scan in get_build_phase_name_containing_file.
+fn build_file_phase_index<'a, O: CommentSource + 'a>(
+    objects: impl Iterator<Item = (&'a str, &'a O)>,
+) -> HashMap<&'a str, (&'a str, Option<&'a str>)> {
     let mut file_to_phase: HashMap<&str, (&str, Option<&str>)> = HashMap::new();
     for (_id, obj) in objects {
-        let isa = obj.get("isa").and_then(|v| v.as_str()).unwrap_or("");
+        let isa = obj.str_prop("isa").unwrap_or("");
         if isa.ends_with("BuildPhase") {
-            let phase_name = obj.get("name").and_then(|v| v.as_str());
-            if let Some(files) = obj.get("files").and_then(|f| f.as_array()) {
+            let phase_name = obj.str_prop("name");
+            if let Some(files) = obj.array_prop("files") {
                 for f in files {
                     if let Some(file_uuid) = f.as_str() {
                         file_to_phase.insert(file_uuid, (isa, phase_name));
@@ -33,42 +122,39 @@ pub fn create_reference_list(project: &PlistValue<'_>) -> HashMap<String, String
             }
         }
     }
-
-    // Process all objects to build comments
-    for (id, object) in objects {
-        get_comment_for_object(id, object, &index, &file_to_phase, &mut cache);
-    }
-
-    cache
+    file_to_phase
 }
 
-fn get_comment_for_object<'a>(
+fn get_comment_for_object<O: CommentSource>(
     id: &str,
-    object: &PlistValue<'a>,
-    objects: &HashMap<&str, &PlistValue<'a>>,
+    object: &O,
+    objects: &HashMap<&str, &O>,
     file_to_phase: &HashMap<&str, (&str, Option<&str>)>,
+    provider: Option<&CommentProvider>,
     cache: &mut HashMap<String, String>,
 ) -> Option<String> {
-    object.as_object()?;
-    let isa = object.get("isa").and_then(|v| v.as_str())?;
+    let isa = object.str_prop("isa")?;
 
     if let Some(cached) = cache.get(id) {
         return Some(cached.clone());
     }
 
-    let comment = if isa == "PBXBuildFile" {
-        get_pbx_build_file_comment(id, object, objects, file_to_phase, cache)
+    let custom = provider.and_then(|provider| object.comment_override(id, provider));
+    let comment = if custom.is_some() {
+        custom
+    } else if isa == "PBXBuildFile" {
+        get_pbx_build_file_comment(id, object, objects, file_to_phase, provider, cache)
     } else if isa == "XCConfigurationList" {
         Some(get_xc_configuration_list_comment(id, objects))
     } else if isa == "XCRemoteSwiftPackageReference" {
-        let repo_url = object.get("repositoryURL").and_then(|v| v.as_str());
+        let repo_url = object.str_prop("repositoryURL");
         if let Some(url) = repo_url {
             Some(format!("{} \"{}\"", isa, get_repo_name_from_url(url)))
         } else {
             Some(isa.to_string())
         }
     } else if isa == "XCLocalSwiftPackageReference" {
-        let path = object.get("relativePath").and_then(|v| v.as_str());
+        let path = object.str_prop("relativePath");
         if let Some(p) = path {
             Some(format!("{} \"{}\"", isa, p))
         } else {
@@ -79,8 +165,8 @@ fn get_comment_for_object<'a>(
     } else if isa.ends_with("BuildPhase") {
         Some(get_build_phase_name(object, isa))
     } else if isa == "PBXGroup" {
-        let has_name = object.get("name").and_then(|v| v.as_str()).is_some();
-        let has_path = object.get("path").and_then(|v| v.as_str()).is_some();
+        let has_name = object.str_prop("name").is_some();
+        let has_path = object.str_prop("path").is_some();
         if !has_name && !has_path {
             Some(String::new())
         } else {
@@ -97,20 +183,20 @@ fn get_comment_for_object<'a>(
     comment
 }
 
-fn get_default_name(obj: &PlistValue<'_>, isa: &str) -> Option<String> {
-    obj.get("name")
-        .and_then(|v| v.as_str())
-        .or_else(|| obj.get("productName").and_then(|v| v.as_str()))
-        .or_else(|| obj.get("path").and_then(|v| v.as_str()))
+fn get_default_name<O: CommentSource>(obj: &O, isa: &str) -> Option<String> {
+    obj.str_prop("name")
+        .or_else(|| obj.str_prop("productName"))
+        .or_else(|| obj.str_prop("path"))
         .map(|s| s.to_string())
         .or_else(|| Some(isa.to_string()))
 }
 
-fn get_pbx_build_file_comment<'a>(
+fn get_pbx_build_file_comment<O: CommentSource>(
     id: &str,
-    build_file: &PlistValue<'a>,
-    objects: &HashMap<&str, &PlistValue<'a>>,
+    build_file: &O,
+    objects: &HashMap<&str, &O>,
     file_to_phase: &HashMap<&str, (&str, Option<&str>)>,
+    provider: Option<&CommentProvider>,
     cache: &mut HashMap<String, String>,
 ) -> Option<String> {
     let build_phase_name = if let Some(&(isa, name)) = file_to_phase.get(id) {
@@ -121,13 +207,12 @@ fn get_pbx_build_file_comment<'a>(
     };
 
     let ref_id = build_file
-        .get("fileRef")
-        .or_else(|| build_file.get("productRef"))
-        .and_then(|v| v.as_str());
+        .str_prop("fileRef")
+        .or_else(|| build_file.str_prop("productRef"));
 
     let name = if let Some(ref_id) = ref_id {
         if let Some(ref_obj) = objects.get(ref_id) {
-            get_comment_for_object(ref_id, ref_obj, objects, file_to_phase, cache)
+            get_comment_for_object(ref_id, *ref_obj, objects, file_to_phase, provider, cache)
                 .unwrap_or_else(|| "(null)".to_string())
         } else {
             "(null)".to_string()
@@ -139,8 +224,8 @@ fn get_pbx_build_file_comment<'a>(
     Some(format!("{} in {}", name, build_phase_name))
 }
 
-fn get_build_phase_name(obj: &PlistValue<'_>, isa: &str) -> String {
-    if let Some(name) = obj.get("name").and_then(|v| v.as_str()) {
+fn get_build_phase_name<O: CommentSource>(obj: &O, isa: &str) -> String {
+    if let Some(name) = obj.str_prop("name") {
         return name.to_string();
     }
     get_default_build_phase_name(isa).unwrap_or_default()
@@ -157,57 +242,49 @@ fn get_default_build_phase_name(isa: &str) -> Option<String> {
     None
 }
 
-fn get_xc_configuration_list_comment<'a>(
-    id: &str,
-    objects: &HashMap<&str, &PlistValue<'a>>,
-) -> String {
+fn get_xc_configuration_list_comment<O: CommentSource>(id: &str, objects: &HashMap<&str, &O>) -> String {
     for (&inner_id, obj) in objects {
-        if obj.as_object().is_some() {
-            let config_list = obj.get("buildConfigurationList").and_then(|v| v.as_str());
-            if config_list == Some(id) {
-                let isa = obj.get("isa").and_then(|v| v.as_str()).unwrap_or("");
-
-                let name = obj
-                    .get("name")
-                    .and_then(|v| v.as_str())
-                    .or_else(|| obj.get("path").and_then(|v| v.as_str()))
-                    .or_else(|| obj.get("productName").and_then(|v| v.as_str()));
-
-                if let Some(name) = name {
-                    return format!("Build configuration list for {} \"{}\"", isa, name);
-                }
+        let config_list = obj.str_prop("buildConfigurationList");
+        if config_list == Some(id) {
+            let isa = obj.str_prop("isa").unwrap_or("");
+
+            let name = obj
+                .str_prop("name")
+                .or_else(|| obj.str_prop("path"))
+                .or_else(|| obj.str_prop("productName"));
 
-                if let Some(targets) = obj.get("targets").and_then(|v| v.as_array()) {
-                    if let Some(first_target_id) = targets.first().and_then(|v| v.as_str()) {
-                        if let Some(target_val) = objects.get(first_target_id) {
-                            let target_name = target_val
-                                .get("productName")
-                                .or_else(|| target_val.get("name"))
-                                .and_then(|v| v.as_str());
-                            if let Some(name) = target_name {
-                                return format!("Build configuration list for {} \"{}\"", isa, name);
-                            }
+            if let Some(name) = name {
+                return format!("Build configuration list for {} \"{}\"", isa, name);
+            }
+
+            if let Some(targets) = obj.array_prop("targets") {
+                if let Some(first_target_id) = targets.first().and_then(|v| v.as_str()) {
+                    if let Some(target_val) = objects.get(first_target_id) {
+                        let target_name = target_val
+                            .str_prop("productName")
+                            .or_else(|| target_val.str_prop("name"));
+                        if let Some(name) = target_name {
+                            return format!("Build configuration list for {} \"{}\"", isa, name);
                         }
                     }
                 }
+            }
 
-                let proxy_name = objects.values().find_map(|val| {
-                    val.as_object()?;
-                    if val.get("isa").and_then(|v| v.as_str()) == Some("PBXContainerItemProxy")
-                        && val.get("containerPortal").and_then(|v| v.as_str()) == Some(inner_id)
-                    {
-                        val.get("remoteInfo").and_then(|v| v.as_str()).map(|s| s.to_string())
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(name) = proxy_name {
-                    return format!("Build configuration list for {} \"{}\"", isa, name);
+            let proxy_name = objects.values().find_map(|val| {
+                if val.str_prop("isa") == Some("PBXContainerItemProxy")
+                    && val.str_prop("containerPortal") == Some(inner_id)
+                {
+                    val.str_prop("remoteInfo").map(|s| s.to_string())
+                } else {
+                    None
                 }
+            });
 
-                return format!("Build configuration list for {}", isa);
+            if let Some(name) = proxy_name {
+                return format!("Build configuration list for {} \"{}\"", isa, name);
             }
+
+            return format!("Build configuration list for {}", isa);
         }
     }
     "Build configuration list for [unknown]".to_string()
@@ -233,6 +310,55 @@ fn get_repo_name_from_url(repo_url: &str) -> String {
     repo_url.to_string()
 }
 
+/// Descriptive comments for `PBXFileSystemSynchronizedBuildFileExceptionSet` objects,
+/// in the form Xcode uses: `Exceptions for "<folder>" folder in "<target>" target`.
+///
+/// Returns (UUID, comment) pairs for exception sets whose owning group and target resolve.
+pub fn get_exception_set_comments(project: &PlistValue<'_>) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let objects = match project.get("objects").and_then(|o| o.as_object()) {
+        Some(o) => o,
+        None => return result,
+    };
+    let index: HashMap<&str, &PlistValue<'_>> = objects.iter().map(|(k, v)| (k.as_ref(), v)).collect();
+
+    for (_id, group) in objects {
+        if group.get("isa").and_then(|v| v.as_str()) != Some("PBXFileSystemSynchronizedRootGroup") {
+            continue;
+        }
+        let folder = match group.get("path").or_else(|| group.get("name")).and_then(|v| v.as_str()) {
+            Some(f) => f,
+            None => continue,
+        };
+        let exceptions = match group.get("exceptions").and_then(|v| v.as_array()) {
+            Some(e) => e,
+            None => continue,
+        };
+        for exception_id in exceptions.iter().filter_map(|v| v.as_str()) {
+            let exception = match index.get(exception_id) {
+                Some(e) => e,
+                None => continue,
+            };
+            if exception.get("isa").and_then(|v| v.as_str()) != Some("PBXFileSystemSynchronizedBuildFileExceptionSet") {
+                continue;
+            }
+            let target_name = exception
+                .get("target")
+                .and_then(|v| v.as_str())
+                .and_then(|t| index.get(t))
+                .and_then(|t| t.get("name"))
+                .and_then(|v| v.as_str());
+            if let Some(target_name) = target_name {
+                result.push((
+                    exception_id.to_string(),
+                    format!("Exceptions for \"{}\" folder in \"{}\" target", folder, target_name),
+                ));
+            }
+        }
+    }
+    result
+}
+
 /// Check if an object's ISA is PBXBuildFile.
 pub fn is_pbx_build_file(isa: &str) -> bool {
     isa == "PBXBuildFile"
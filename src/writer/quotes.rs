@@ -25,6 +25,20 @@ pub fn add_quotes(s: &str) -> String {
     result
 }
 
+/// Escape a string for output between single quotes: like [`add_quotes`], but
+/// `'` is escaped and `"` is left as-is.
+pub fn add_single_quotes(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\'' => result.push_str("\\'"),
+            '"' => result.push('"'),
+            c => result.push_str(&add_quotes(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    result
+}
+
 /// Ensure a string value is properly quoted for .pbxproj output.
 ///
 /// Matches `ensureQuotes` from writer.ts:
@@ -77,6 +91,13 @@ mod tests {
         assert_eq!(add_quotes("\x1F"), "\\U001f");
     }
 
+    #[test]
+    fn test_add_single_quotes() {
+        assert_eq!(add_single_quotes("it's"), "it\\'s");
+        assert_eq!(add_single_quotes(r#"say "hi""#), r#"say "hi""#);
+        assert_eq!(add_single_quotes("a\nb"), "a\\nb");
+    }
+
     #[test]
     fn test_ensure_quotes_safe() {
         assert_eq!(ensure_quotes("hello"), "hello");
@@ -1,35 +1,128 @@
 use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
 
-use super::comments::{create_reference_list, is_pbx_build_file, is_pbx_file_reference};
-use super::quotes::{add_quotes, format_data};
+use super::comments::{
+    create_reference_list_with, get_exception_set_comments, is_pbx_build_file, is_pbx_file_reference, CommentProvider,
+};
+use super::quotes::{add_quotes, add_single_quotes, format_data};
+use crate::parser::escape::unicode_to_nextstep;
+use crate::parser::parser::{QuoteSite, QuoteStyle, QuoteStyles, DEFAULT_MAX_DEPTH};
+use crate::types::constants::XCODE_16_OBJECT_VERSION;
 use crate::types::plist::PlistObject;
 use crate::types::PlistValue;
 
 /// Options for the writer.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct WriterOptions {
     pub tab: String,
-    pub shebang: String,
+    /// Text of the `// !$*UTF8*$!` comment line at the top; `None` omits the line.
+    pub shebang: Option<String>,
+    /// Follow Xcode's own output conventions instead of writer.ts's (see [`format_like_xcode`]).
+    pub xcode_style: bool,
+    /// Keep objects in their original order within each ISA section instead of
+    /// sorting by UUID. Objects added after parsing are written at the end of their section.
+    pub preserve_object_order: bool,
+    /// Write characters that have a NeXTSTEP encoding as `\NNN` octal escapes
+    /// (e.g. `©` as `\240`), the way the parser reads them, instead of literal UTF-8.
+    pub nextstep_octal: bool,
+    /// Quote styles to reproduce, from [`parse_with_quote_styles`](crate::parser::parse_with_quote_styles).
+    /// Single-quoted strings are written with single quotes and double-quoted strings
+    /// keep their quotes even where the writer would otherwise drop them.
+    pub quote_styles: QuoteStyles,
+    /// Consulted before the default logic for every object's inline comment; returning
+    /// `None` keeps the default. Lets callers relabel objects, e.g. with target membership.
+    pub comment_provider: Option<CommentProvider>,
+}
+
+impl std::fmt::Debug for WriterOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriterOptions")
+            .field("tab", &self.tab)
+            .field("shebang", &self.shebang)
+            .field("xcode_style", &self.xcode_style)
+            .field("preserve_object_order", &self.preserve_object_order)
+            .field("nextstep_octal", &self.nextstep_octal)
+            .field("quote_styles", &self.quote_styles)
+            .field("comment_provider", &self.comment_provider.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 impl Default for WriterOptions {
     fn default() -> Self {
         WriterOptions {
             tab: "\t".to_string(),
-            shebang: "!$*UTF8*$!".to_string(),
+            shebang: Some("!$*UTF8*$!".to_string()),
+            xcode_style: false,
+            preserve_object_order: false,
+            nextstep_octal: false,
+            quote_styles: QuoteStyles::new(),
+            comment_provider: None,
+        }
+    }
+}
+
+/// First `LastUpgradeCheck` of Xcode 16.1, which writes synchronized root groups on one line.
+const XCODE_16_1_UPGRADE_CHECK: i64 = 1610;
+
+/// Formatting decisions derived from `WriterOptions::xcode_style` and the project itself.
+#[derive(Debug, Clone, Copy, Default)]
+struct Style {
+    /// Quote strings containing `:` or `___`.
+    strict_quotes: bool,
+    /// Write integers as-is, without appending `.0` to version-like keys.
+    verbatim_numbers: bool,
+    /// Write empty dictionaries as `{` / `}` on two lines instead of `{}`.
+    open_empty_objects: bool,
+    /// Write `PBXFileSystemSynchronizedRootGroup` objects on a single line.
+    inline_sync_groups: bool,
+    /// Annotate references inside single-line objects' arrays with their comments.
+    commented_inline_arrays: bool,
+}
+
+impl Style {
+    fn for_project(project: &PlistValue<'_>, xcode_style: bool) -> Self {
+        if !xcode_style {
+            return Style::default();
+        }
+        let object_version = project.get("objectVersion").and_then(as_lenient_int).unwrap_or(0);
+        let last_upgrade_check = project
+            .get("rootObject")
+            .and_then(|v| v.as_str())
+            .and_then(|root| project.get("objects")?.get(root))
+            .and_then(|root| root.get("attributes")?.get("LastUpgradeCheck"))
+            .and_then(as_lenient_int)
+            .unwrap_or(0);
+        let xcode_16_format = object_version >= XCODE_16_OBJECT_VERSION;
+        Style {
+            strict_quotes: true,
+            verbatim_numbers: true,
+            open_empty_objects: !xcode_16_format,
+            inline_sync_groups: xcode_16_format && last_upgrade_check >= XCODE_16_1_UPGRADE_CHECK,
+            commented_inline_arrays: true,
         }
     }
 }
 
+/// Read an integer that may have been parsed as either a number or a string (e.g. `"0730"`).
+fn as_lenient_int(value: &PlistValue<'_>) -> Option<i64> {
+    value
+        .as_integer()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
 /// Serializes a PlistValue (representing a parsed .pbxproj) back to text format.
 pub struct Writer {
     buf: String,
     indent: usize,
     comments: HashMap<String, String>,
     options: WriterOptions,
+    style: Style,
     // Pre-computed indent strings for levels 0..MAX_INDENT
     indents: Vec<String>,
+    /// Keys and array indices leading to the value being written, tracked only
+    /// when there are `quote_styles` to look up.
+    quote_path: Vec<String>,
 }
 
 const MAX_CACHED_INDENT: usize = 8;
@@ -49,12 +142,20 @@ impl Writer {
         // Estimate output size: typically ~1.05x input representation
         let estimated_size = estimate_size(project);
 
+        let style = Style::for_project(project, options.xcode_style);
+        let mut comments = create_reference_list_with(project, options.comment_provider.as_ref());
+        if style.open_empty_objects {
+            comments.extend(get_exception_set_comments(project));
+        }
+
         let mut writer = Writer {
             buf: String::with_capacity(estimated_size),
             indent: 0,
-            comments: create_reference_list(project),
+            comments,
             options,
+            style,
             indents,
+            quote_path: Vec::new(),
         };
         writer.write_shebang();
         writer.write_project(project);
@@ -89,16 +190,98 @@ impl Writer {
 
     /// Write a formatted ID with optional comment. Writes directly to buf.
     fn write_format_id(&mut self, id: &str) {
+        self.write_format_id_at(id, false);
+    }
+
+    /// [`write_format_id`](Self::write_format_id) for a dictionary key.
+    fn write_format_key(&mut self, id: &str) {
+        self.write_format_id_at(id, true);
+    }
+
+    fn write_format_id_at(&mut self, id: &str, is_key: bool) {
         if let Some(comment) = self.comments.get(id) {
             if !comment.is_empty() {
-                self.buf.push_str(id);
+                if self.style.strict_quotes && id.contains("___") {
+                    let _ = write!(self.buf, "\"{}\"", id);
+                } else {
+                    self.buf.push_str(id);
+                }
                 self.buf.push_str(" /* ");
                 self.buf.push_str(comment);
                 self.buf.push_str(" */");
                 return;
             }
         }
-        write_ensure_quotes_to(&mut self.buf, id);
+        self.write_quoted_at(id, is_key);
+    }
+
+    /// Write a string value, quoting it if the active style requires.
+    #[inline]
+    fn write_quoted(&mut self, value: &str) {
+        self.write_quoted_at(value, false);
+    }
+
+    /// Write a dictionary key, quoting it if the active style requires.
+    #[inline]
+    fn write_key(&mut self, key: &str) {
+        self.write_quoted_at(key, true);
+    }
+
+    fn write_quoted_at(&mut self, value: &str, is_key: bool) {
+        if !self.options.quote_styles.is_empty() {
+            let site = QuoteSite {
+                path: self.quote_path.clone(),
+                is_key,
+            };
+            match self.options.quote_styles.get(&site) {
+                Some(QuoteStyle::Single) => {
+                    self.buf.push('\'');
+                    self.buf.push_str(&add_single_quotes(value));
+                    self.buf.push('\'');
+                    return;
+                }
+                Some(QuoteStyle::Double) if is_safe_unquoted(value) => {
+                    self.buf.push('"');
+                    self.buf.push_str(value);
+                    self.buf.push('"');
+                    return;
+                }
+                _ => {}
+            }
+        }
+        if self.options.nextstep_octal && value.chars().any(|c| unicode_to_nextstep(c).is_some()) {
+            self.buf.push('"');
+            for ch in add_quotes(value).chars() {
+                match unicode_to_nextstep(ch) {
+                    Some(byte) => {
+                        let _ = write!(self.buf, "\\{:03o}", byte);
+                    }
+                    None => self.buf.push(ch),
+                }
+            }
+            self.buf.push('"');
+        } else if self.style.strict_quotes && (value.contains(':') || value.contains("___")) {
+            self.buf.push('"');
+            self.buf.push_str(&add_quotes(value));
+            self.buf.push('"');
+        } else {
+            write_ensure_quotes_to(&mut self.buf, value);
+        }
+    }
+
+    /// Descend into `segment` (a key or array index) of the value being written.
+    #[inline]
+    fn enter(&mut self, segment: impl std::fmt::Display) {
+        if !self.options.quote_styles.is_empty() {
+            self.quote_path.push(segment.to_string());
+        }
+    }
+
+    #[inline]
+    fn leave(&mut self) {
+        if !self.options.quote_styles.is_empty() {
+            self.quote_path.pop();
+        }
     }
 
     fn key_has_float_value(key: &str) -> bool {
@@ -112,9 +295,12 @@ impl Writer {
     // ── Structure writers ──────────────────────────────────────────
 
     fn write_shebang(&mut self) {
+        let Some(shebang) = self.options.shebang.clone() else {
+            return;
+        };
         self.write_indent();
         self.buf.push_str("// ");
-        self.buf.push_str(&self.options.shebang);
+        self.buf.push_str(&shebang);
         self.buf.push('\n');
     }
 
@@ -130,11 +316,12 @@ impl Writer {
 
     fn write_object(&mut self, object: &PlistObject<'_>, is_base: bool) {
         for (key, value) in object {
+            self.enter(key);
             match value {
                 PlistValue::Data(data) => {
                     let d = format_data(data);
                     self.write_indent();
-                    write_ensure_quotes_to(&mut self.buf, key);
+                    self.write_key(key);
                     self.buf.push_str(" = ");
                     self.buf.push_str(&d);
                     self.buf.push_str(";\n");
@@ -142,15 +329,14 @@ impl Writer {
                 PlistValue::Array(items) => {
                     self.write_array(key, items);
                 }
+                PlistValue::Object(inner) if !is_base && inner.is_empty() && !self.style.open_empty_objects => {
+                    self.write_indent();
+                    self.write_key(key);
+                    self.buf.push_str(" = {};\n");
+                }
                 PlistValue::Object(inner) => {
-                    if !is_base && inner.is_empty() {
-                        self.write_indent();
-                        write_ensure_quotes_to(&mut self.buf, key);
-                        self.buf.push_str(" = {};\n");
-                        continue;
-                    }
                     self.write_indent();
-                    write_ensure_quotes_to(&mut self.buf, key);
+                    self.write_key(key);
                     self.buf.push_str(" = {\n");
                     self.indent += 1;
                     if is_base && key == "objects" {
@@ -163,9 +349,9 @@ impl Writer {
                 }
                 PlistValue::Integer(n) => {
                     self.write_indent();
-                    write_ensure_quotes_to(&mut self.buf, key);
+                    self.write_key(key);
                     self.buf.push_str(" = ");
-                    if Self::key_has_float_value(key) {
+                    if Self::key_has_float_value(key) && !self.style.verbatim_numbers {
                         let _ = write!(self.buf, "{}.0", n);
                     } else {
                         let _ = write!(self.buf, "{}", n);
@@ -174,7 +360,7 @@ impl Writer {
                 }
                 PlistValue::Float(f) => {
                     self.write_indent();
-                    write_ensure_quotes_to(&mut self.buf, key);
+                    self.write_key(key);
                     self.buf.push_str(" = ");
                     if Self::key_has_float_value(key) && f.fract() == 0.0 {
                         let _ = write!(self.buf, "{}.0", *f as i64);
@@ -185,16 +371,17 @@ impl Writer {
                 }
                 PlistValue::String(s) => {
                     self.write_indent();
-                    write_ensure_quotes_to(&mut self.buf, key);
+                    self.write_key(key);
                     self.buf.push_str(" = ");
                     if key == "remoteGlobalIDString" || key == "TestTargetID" {
-                        write_ensure_quotes_to(&mut self.buf, s);
+                        self.write_quoted(s);
                     } else {
                         self.write_format_id(s);
                     }
                     self.buf.push_str(";\n");
                 }
             }
+            self.leave();
         }
     }
 
@@ -219,7 +406,9 @@ impl Writer {
             self.buf.push('\n');
             let _ = write!(self.buf, "/* Begin {} section */\n", isa);
 
-            entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            if !self.options.preserve_object_order {
+                entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            }
 
             for &(id, obj) in entries.iter() {
                 self.write_object_inclusive(id, obj);
@@ -235,17 +424,22 @@ impl Writer {
             .find(|(k, _)| k.as_ref() == "isa")
             .and_then(|(_, v)| v.as_str())
             .unwrap_or("");
-        if is_pbx_build_file(isa) || is_pbx_file_reference(isa) {
+        self.enter(key);
+        if is_pbx_build_file(isa)
+            || is_pbx_file_reference(isa)
+            || (self.style.inline_sync_groups && isa == "PBXFileSystemSynchronizedRootGroup")
+        {
             self.write_object_inline(key, value);
-            return;
+        } else {
+            self.write_indent();
+            self.write_format_key(key);
+            self.buf.push_str(" = {\n");
+            self.indent += 1;
+            self.write_object(value, false);
+            self.indent -= 1;
+            self.write_line("};");
         }
-        self.write_indent();
-        self.write_format_id(key);
-        self.buf.push_str(" = {\n");
-        self.indent += 1;
-        self.write_object(value, false);
-        self.indent -= 1;
-        self.write_line("};");
+        self.leave();
     }
 
     /// Write an object on a single line (for PBXBuildFile and PBXFileReference).
@@ -261,74 +455,104 @@ impl Writer {
     }
 
     fn write_inline_recursive(&mut self, key: &str, value: &PlistObject<'_>) {
-        self.write_format_id(key);
-        self.buf.push_str(" = {");
+        self.write_format_key(key);
+        self.buf.push_str(" = ");
+        self.write_inline_object(value);
+        self.buf.push_str("; ");
+    }
+
+    fn write_inline_object(&mut self, value: &PlistObject<'_>) {
+        self.buf.push('{');
 
         for (k, v) in value {
+            self.enter(k);
             match v {
                 PlistValue::Data(data) => {
                     let d = format_data(data);
-                    write_ensure_quotes_to(&mut self.buf, k);
+                    self.write_key(k);
                     self.buf.push_str(" = ");
                     self.buf.push_str(&d);
                     self.buf.push_str("; ");
                 }
                 PlistValue::Array(items) => {
-                    write_ensure_quotes_to(&mut self.buf, k);
-                    self.buf.push_str(" = (");
-                    for item in items {
-                        match item {
-                            PlistValue::String(s) => {
-                                write_ensure_quotes_to(&mut self.buf, s);
-                                self.buf.push_str(", ");
-                            }
-                            PlistValue::Integer(n) => {
-                                let _ = write!(self.buf, "{}", n);
-                                self.buf.push_str(", ");
-                            }
-                            _ => {}
-                        }
-                    }
-                    self.buf.push_str("); ");
+                    self.write_key(k);
+                    self.buf.push_str(" = ");
+                    self.write_inline_array(items);
+                    self.buf.push_str("; ");
                 }
                 PlistValue::Object(inner) => {
                     self.write_inline_recursive(k, inner);
                 }
                 PlistValue::String(s) => {
-                    write_ensure_quotes_to(&mut self.buf, k);
+                    self.write_key(k);
                     self.buf.push_str(" = ");
                     if k == "remoteGlobalIDString" || k == "TestTargetID" {
-                        write_ensure_quotes_to(&mut self.buf, s);
+                        self.write_quoted(s);
                     } else {
                         self.write_format_id(s);
                     }
                     self.buf.push_str("; ");
                 }
                 PlistValue::Integer(n) => {
-                    write_ensure_quotes_to(&mut self.buf, k);
+                    self.write_key(k);
                     self.buf.push_str(" = ");
                     let _ = write!(self.buf, "{}", n);
                     self.buf.push_str("; ");
                 }
                 PlistValue::Float(f) => {
-                    write_ensure_quotes_to(&mut self.buf, k);
+                    self.write_key(k);
                     self.buf.push_str(" = ");
                     let _ = write!(self.buf, "{}", f);
                     self.buf.push_str("; ");
                 }
             }
+            self.leave();
         }
 
-        self.buf.push_str("}; ");
+        self.buf.push('}');
+    }
+
+    /// Inline counterpart of [`write_array_items`](Self::write_array_items): every
+    /// variant is written, nested arrays and objects included.
+    fn write_inline_array(&mut self, items: &[PlistValue<'_>]) {
+        self.buf.push('(');
+        for (i, item) in items.iter().enumerate() {
+            self.enter(i);
+            match item {
+                PlistValue::Data(data) => {
+                    let d = format_data(data);
+                    self.buf.push_str(&d);
+                }
+                PlistValue::Object(inner) => self.write_inline_object(inner),
+                PlistValue::String(s) if self.style.commented_inline_arrays => self.write_format_id(s),
+                PlistValue::String(s) => self.write_quoted(s),
+                PlistValue::Integer(n) => {
+                    let _ = write!(self.buf, "{}", n);
+                }
+                PlistValue::Float(f) => {
+                    let _ = write!(self.buf, "{}", f);
+                }
+                PlistValue::Array(inner) => self.write_inline_array(inner),
+            }
+            self.buf.push_str(", ");
+            self.leave();
+        }
+        self.buf.push(')');
     }
 
     fn write_array(&mut self, key: &str, items: &[PlistValue<'_>]) {
         self.write_indent();
-        write_ensure_quotes_to(&mut self.buf, key);
+        self.write_key(key);
         self.buf.push_str(" = (\n");
         self.indent += 1;
+        self.write_array_items(items);
+        self.indent -= 1;
+        self.write_line(");");
+    }
 
-        for item in items {
+    fn write_array_items(&mut self, items: &[PlistValue<'_>]) {
+        for (i, item) in items.iter().enumerate() {
+            self.enter(i);
             match item {
                 PlistValue::Data(data) => {
                     let d = format_data(data);
@@ -358,12 +582,16 @@ impl Writer {
                     let _ = write!(self.buf, "{}", f);
                     self.buf.push_str(",\n");
                 }
-                _ => {}
+                PlistValue::Array(inner) => {
+                    self.write_line("(");
+                    self.indent += 1;
+                    self.write_array_items(inner);
+                    self.indent -= 1;
+                    self.write_line("),");
+                }
             }
+            self.leave();
         }
-
-        self.indent -= 1;
-        self.write_line(");");
     }
 }
 
@@ -412,11 +640,84 @@ fn estimate_size(value: &PlistValue<'_>) -> usize {
     }
 }
 
+/// Nesting depth of a PlistValue tree, computed without recursion so that
+/// arbitrarily deep input can't overflow the stack.
+fn nesting_depth(value: &PlistValue<'_>) -> usize {
+    let mut max = 0;
+    let mut stack = vec![(value, 0usize)];
+    while let Some((v, depth)) = stack.pop() {
+        match v {
+            PlistValue::Object(pairs) => {
+                max = max.max(depth + 1);
+                stack.extend(pairs.iter().map(|(_, inner)| (inner, depth + 1)));
+            }
+            PlistValue::Array(items) => {
+                max = max.max(depth + 1);
+                stack.extend(items.iter().map(|inner| (inner, depth + 1)));
+            }
+            _ => {}
+        }
+    }
+    max
+}
+
 /// Build a .pbxproj string from a PlistValue.
 pub fn build(project: &PlistValue<'_>) -> String {
     Writer::new(project).get_results()
 }
 
+/// Build a .pbxproj string, keeping each section's objects in their original order.
+///
+/// Useful for files written by tools that don't sort by UUID, where [`build`]
+/// would reorder every section and produce a noisy diff.
+pub fn build_preserving_order(project: &PlistValue<'_>) -> String {
+    let options = WriterOptions {
+        preserve_object_order: true,
+        ..WriterOptions::default()
+    };
+    Writer::with_options(project, options).get_results()
+}
+
+/// Build a .pbxproj string, reproducing the quote styles recorded by
+/// [`parse_with_quote_styles`](crate::parser::parse_with_quote_styles).
+pub fn build_with_quote_styles(project: &PlistValue<'_>, quote_styles: QuoteStyles) -> String {
+    let options = WriterOptions {
+        quote_styles,
+        ..WriterOptions::default()
+    };
+    Writer::with_options(project, options).get_results()
+}
+
+/// Build a .pbxproj string following Xcode's own conventions where they differ from
+/// writer.ts (which [`build`] replicates):
+///
+/// - Strings containing `:` or `___` are quoted (`"https://…"`, `"___RootGroup_"`).
+/// - Integer values of version-like keys are written as-is (`SWIFT_VERSION = 5`), not as `5.0`.
+/// - Projects older than the Xcode 16 format write empty dictionaries open
+///   (`{` / `}` on two lines), and name synchronized-group exception sets
+///   `Exceptions for "<folder>" folder in "<target>" target`.
+/// - Xcode 16 format projects last upgraded by Xcode 16.1 or later write
+///   `PBXFileSystemSynchronizedRootGroup` objects on a single line.
+/// - References in the arrays of single-line objects carry their comments.
+pub fn format_like_xcode(project: &PlistValue<'_>) -> String {
+    let options = WriterOptions {
+        xcode_style: true,
+        ..WriterOptions::default()
+    };
+    Writer::with_options(project, options).get_results()
+}
+
+/// Build a .pbxproj string, rejecting trees nested deeper than the parser would accept.
+///
+/// Use this instead of [`build`] for values that didn't come from [`crate::parser::parse`]
+/// (e.g. deserialized JSON), since the writer recurses once per nesting level.
+pub fn try_build(project: &PlistValue<'_>) -> Result<String, String> {
+    if nesting_depth(project) > DEFAULT_MAX_DEPTH {
+        return Err(format!("Maximum nesting depth of {} exceeded", DEFAULT_MAX_DEPTH));
+    }
+    Ok(build(project))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,4 +750,118 @@ mod tests {
         assert!(!Writer::key_has_float_value("name"));
         assert!(!Writer::key_has_float_value("swift_version")); // lowercase
     }
+
+    #[test]
+    fn test_try_build_rejects_deep_nesting() {
+        let mut value = PlistValue::Object(vec![]);
+        for _ in 0..2000 {
+            value = PlistValue::Object(vec![(Cow::Borrowed("a"), value)]);
+        }
+        assert!(try_build(&value).is_err());
+        assert!(try_build(&make_simple_project()).is_ok());
+    }
+
+    #[test]
+    fn test_nextstep_octal_round_trip() {
+        let input = "// !$*UTF8*$!\n{\n\tname = \"\\240 2024 Acme\\341\";\n}\n";
+        let parsed = crate::parser::parse(input).unwrap();
+        assert_eq!(
+            parsed.get("name").and_then(|v| v.as_str()),
+            Some("\u{00a9} 2024 Acme\u{00c6}")
+        );
+
+        // Default keeps literal UTF-8
+        assert!(build(&parsed).contains("name = \"\u{00a9} 2024 Acme\u{00c6}\";"));
+
+        let options = WriterOptions {
+            nextstep_octal: true,
+            ..WriterOptions::default()
+        };
+        let output = Writer::with_options(&parsed, options).get_results();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_nested_array_round_trip() {
+        let parsed = crate::parser::parse("{ key = ( ( a, b ), ( c ) ); }").unwrap();
+        let output = build(&parsed);
+        assert!(output.contains("\tkey = (\n\t\t(\n\t\t\ta,\n\t\t\tb,\n\t\t),\n\t\t(\n\t\t\tc,\n\t\t),\n\t);\n"));
+        assert_eq!(crate::parser::parse(&output).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_inline_object_keeps_every_array_item() {
+        let input = "{ objects = { \
+            AAAAAAAAAAAAAAAAAAAAAAAA = { isa = PBXBuildFile; fileRef = BBBBBBBBBBBBBBBBBBBBBBBB; \
+                settings = { ATTRIBUTES = ( ( Weak, \"\" ), \"\", 1.5, <0fbd77>, { k = v; } ); COMPILER_FLAGS = \"\"; }; }; \
+        }; }";
+        let parsed = crate::parser::parse(input).unwrap();
+        let output = build(&parsed);
+        assert!(output.contains(
+            "settings = {ATTRIBUTES = ((Weak, \"\", ), \"\", 1.5, <0FBD77>, {k = v; }, ); COMPILER_FLAGS = \"\"; }; };"
+        ));
+        assert_eq!(crate::parser::parse(&output).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_comment_provider_overrides_one_object() {
+        let input = "{ objects = { \
+            AAAAAAAAAAAAAAAAAAAAAAAA = { isa = PBXFileReference; path = a.swift; sourceTree = \"<group>\"; }; \
+            BBBBBBBBBBBBBBBBBBBBBBBB = { isa = PBXFileReference; path = b.swift; sourceTree = \"<group>\"; }; \
+        }; }";
+        let parsed = crate::parser::parse(input).unwrap();
+        let default = build(&parsed);
+        assert!(default.contains("AAAAAAAAAAAAAAAAAAAAAAAA /* a.swift */ = {"));
+
+        let options = WriterOptions {
+            comment_provider: Some(std::sync::Arc::new(|uuid: &str, _: &PlistObject<'_>| {
+                (uuid == "AAAAAAAAAAAAAAAAAAAAAAAA").then(|| "a.swift (App)".to_string())
+            })),
+            ..WriterOptions::default()
+        };
+        let output = Writer::with_options(&parsed, options).get_results();
+        assert!(output.contains("AAAAAAAAAAAAAAAAAAAAAAAA /* a.swift (App) */ = {"));
+        assert!(output.contains("BBBBBBBBBBBBBBBBBBBBBBBB /* b.swift */ = {"));
+        assert_eq!(output.replace(" (App)", ""), default);
+    }
+
+    #[test]
+    fn test_suppressed_shebang_round_trips() {
+        let input = "// !$*UTF8*$!\n{\n\tarchiveVersion = 1;\n\tobjects = {\n\t};\n}\n";
+        let parsed = crate::parser::parse(input).unwrap();
+        let options = WriterOptions {
+            shebang: None,
+            ..WriterOptions::default()
+        };
+        let output = Writer::with_options(&parsed, options).get_results();
+        assert!(output.starts_with('{'));
+        assert_eq!(format!("// !$*UTF8*$!\n{}", output), build(&parsed));
+        assert_eq!(crate::parser::parse(&output).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_quote_styles_round_trip_per_occurrence() {
+        let input = "// !$*UTF8*$!\n{\n\ta = Foo;\n\tb = 'Foo';\n\tc = \"Bar\";\n\td = Bar;\n\tlist = (\n\t\t'Bar',\n\t\tBar,\n\t);\n}\n";
+        let (parsed, quote_styles) = crate::parser::parse_with_quote_styles(input).unwrap();
+        assert_eq!(build_with_quote_styles(&parsed, quote_styles), input);
+    }
+
+    #[test]
+    fn test_source_comments_are_regenerated() {
+        // The parser keeps no comments, so stale or hand-written ones never reach the
+        // output; the writer always derives them from the objects themselves.
+        let input = "// !$*UTF8*$!\n{ objects = { \
+            AAAAAAAAAAAAAAAAAAAAAAAA /* old.swift */ = { isa = PBXFileReference; path = a.swift; sourceTree = \"<group>\"; }; \
+            /* a note someone left */ \
+        }; rootObject = AAAAAAAAAAAAAAAAAAAAAAAA /* Project object */; }";
+        let parsed = crate::parser::parse(input).unwrap();
+        let output = build(&parsed);
+        assert!(output.contains("AAAAAAAAAAAAAAAAAAAAAAAA /* a.swift */ = {"));
+        assert!(!output.contains("old.swift"));
+        assert!(!output.contains("a note someone left"));
+
+        let (styled, quote_styles) = crate::parser::parse_with_quote_styles(input).unwrap();
+        assert_eq!(styled, parsed);
+        assert_eq!(build_with_quote_styles(&styled, quote_styles), output);
+    }
 }
@@ -3,7 +3,7 @@
 /// These tests mirror the original TypeScript test suite from @bacons/xcode.
 use xcode::parser::parse;
 use xcode::types::plist::PlistValue;
-use xcode::writer::serializer::build;
+use xcode::writer::serializer::{build, format_like_xcode};
 
 mod fixture_tests {
     use super::*;
@@ -19,6 +19,7 @@ mod fixture_tests {
         "007-xcode16.pbxproj",
         "008-out-of-order-orphans.pbxproj",
         "009-expo-app-clip.pbxproj",
+        "010-single-quotes.pbxproj",
         "shopify-tophat.pbxproj",
         "AFNetworking.pbxproj",
         "project.pbxproj",
@@ -52,6 +53,13 @@ mod fixture_tests {
         "watch.pbxproj",
     ];
 
+    /// Fixtures written by Xcode itself that should round-trip through `format_like_xcode`.
+    const XCODE_IN_OUT_FIXTURES: &[&str] = &[
+        "008-out-of-order-orphans.pbxproj",
+        "009-expo-app-clip.pbxproj",
+        "shopify-tophat.pbxproj",
+    ];
+
     #[test]
     fn test_all_fixtures_parse() {
         for fixture in ALL_FIXTURES {
@@ -66,14 +74,29 @@ mod fixture_tests {
 
     #[test]
     fn test_round_trip_fixtures() {
+        assert_round_trips(IN_OUT_FIXTURES, build);
+    }
+
+    #[test]
+    fn test_round_trip_fixtures_like_xcode() {
+        assert_round_trips(XCODE_IN_OUT_FIXTURES, format_like_xcode);
+    }
+
+    fn assert_round_trips(fixtures: &[&str], write: fn(&PlistValue<'_>) -> String) {
         let mut passed = Vec::new();
         let mut failed = Vec::new();
 
-        for fixture in IN_OUT_FIXTURES {
+        for fixture in fixtures {
             let path = Path::new(FIXTURES_DIR).join(fixture);
-            let original = fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", fixture, e));
+            let mut original =
+                fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {}", fixture, e));
             let parsed = parse(&original).unwrap_or_else(|e| panic!("Failed to parse {}: {}", fixture, e));
-            let output = build(&parsed);
+            let output = write(&parsed);
+            // The writers always end the file with a newline, as Xcode does; a fixture
+            // saved without one (shopify-tophat) is compared as if it had it.
+            if !original.ends_with('\n') {
+                original.push('\n');
+            }
 
             if output == original {
                 passed.push(*fixture);
@@ -106,17 +129,33 @@ mod fixture_tests {
             }
         }
 
-        eprintln!(
-            "\nRound-trip results: {}/{} passed",
-            passed.len(),
-            IN_OUT_FIXTURES.len()
-        );
+        eprintln!("\nRound-trip results: {}/{} passed", passed.len(), fixtures.len());
         for f in &passed {
             eprintln!("  PASS: {}", f);
         }
         for f in &failed {
             eprintln!("  FAIL: {}", f);
         }
+        assert!(failed.is_empty(), "Round-trip failed for {:?}", failed);
+    }
+
+    #[test]
+    fn test_parse_stats_object_count() {
+        use std::collections::HashSet;
+        use xcode::parser::parse_with_stats;
+        use xcode::project::XcodeProject;
+
+        let path = Path::new(FIXTURES_DIR).join("AFNetworking.pbxproj");
+        let content = fs::read_to_string(&path).unwrap();
+        let (_, stats) = parse_with_stats(&content).unwrap();
+        let project = XcodeProject::from_plist(&content).unwrap();
+
+        let isas: HashSet<&str> = project.objects().map(|(_, obj)| obj.isa.as_str()).collect();
+        let total: usize = isas.iter().map(|isa| project.objects_by_isa(isa).len()).sum();
+        assert_eq!(stats.object_count, total);
+        assert!(stats.token_count > stats.object_count);
+        assert!(stats.max_depth >= 3);
+        assert!(stats.bytes_consumed <= content.len());
     }
 
     #[test]
@@ -127,6 +166,58 @@ mod fixture_tests {
         let inner = result.get("456").unwrap();
         assert_eq!(inner.get("789").and_then(|v| v.as_str()), Some("def"));
     }
+
+    #[test]
+    fn test_parse_with_bom_and_leading_trivia() {
+        let content = fs::read_to_string(Path::new(FIXTURES_DIR).join("project.pbxproj")).unwrap();
+        let expected = parse(&content).unwrap();
+
+        let with_bom = format!("\u{FEFF}{}", content);
+        assert_eq!(parse(&with_bom).unwrap(), expected);
+
+        let with_trivia = format!("\u{FEFF}\n  /* generated */\n// note\n{}", content);
+        assert_eq!(parse(&with_trivia).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_round_trip_preserving_quote_styles() {
+        use xcode::parser::parse_with_quote_styles;
+        use xcode::writer::serializer::build_with_quote_styles;
+
+        let original = fs::read_to_string(Path::new(FIXTURES_DIR).join("010-single-quotes.pbxproj")).unwrap();
+        let (parsed, styles) = parse_with_quote_styles(&original).unwrap();
+        assert_ne!(build(&parsed), original);
+        assert_eq!(build_with_quote_styles(&parsed, styles), original);
+    }
+
+    /// UUIDs in a section, in the order they appear in the file.
+    fn section_ids(text: &str, isa: &str) -> Vec<String> {
+        let begin = format!("/* Begin {} section */", isa);
+        let end = format!("/* End {} section */", isa);
+        let section = text.split(&begin).nth(1).unwrap().split(&end).next().unwrap();
+        section
+            .lines()
+            .filter(|l| l.starts_with("\t\t") && !l.starts_with("\t\t\t"))
+            .map(|l| l.trim().split(' ').next().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_preserving_order() {
+        use xcode::writer::serializer::build_preserving_order;
+
+        let original = fs::read_to_string(Path::new(FIXTURES_DIR).join("malformed.pbxproj")).unwrap();
+        let parsed = parse(&original).unwrap();
+        let original_ids = section_ids(&original, "PBXFileReference");
+
+        let mut sorted = original_ids.clone();
+        sorted.sort();
+        assert_ne!(original_ids, sorted, "fixture section should not be in UUID order");
+
+        let preserved = build_preserving_order(&parsed);
+        assert_eq!(section_ids(&preserved, "PBXFileReference"), original_ids);
+        assert_eq!(section_ids(&build(&parsed), "PBXFileReference"), sorted);
+    }
 }
 
 mod unicode_tests {
@@ -309,6 +400,21 @@ mod unicode_tests {
         assert_eq!(result.get("empty2").and_then(|v| v.as_str()), Some(""));
     }
 
+    #[test]
+    fn test_array_items_survive_round_trip() {
+        let input = "{\n\titems = (\n\t\t\"\",\n\t\ta,\n\t\t(\n\t\t\tb,\n\t\t\t\"\",\n\t\t),\n\t\t3,\n\t);\n}";
+        let parsed = parse(input).unwrap();
+        let output = build(&parsed);
+        assert!(output.contains("\t\t\"\",\n\t\ta,\n"));
+        let reparsed = parse(&output).unwrap();
+        assert_eq!(reparsed, parsed);
+
+        let items = reparsed.get("items").and_then(|v| v.as_array()).unwrap();
+        assert_eq!(items.len(), 4);
+        assert_eq!(items[0].as_str(), Some(""));
+        assert_eq!(items[2].as_array().map(|inner| inner.len()), Some(2));
+    }
+
     #[test]
     fn test_mixed_quote_styles() {
         let input = r#"{
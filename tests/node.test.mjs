@@ -137,6 +137,12 @@ describe.skipIf(!native)("native napi", () => {
     expect(output).toBe(original);
   });
 
+  test("build() rejects input nested past the depth limit", () => {
+    let value = {};
+    for (let i = 0; i < 600; i++) value = { child: value };
+    expect(() => native.build({ archiveVersion: 1, objects: value })).toThrow(/nesting depth/);
+  });
+
   test("clean project has no orphaned references", () => {
     const project = native.XcodeProject.open(join(FIXTURES_DIR, "project.pbxproj"));
     const orphans = project.findOrphanedReferences();
@@ -181,6 +187,7 @@ describe.skipIf(!native)("native napi", () => {
     expect(known).toBeTruthy();
     expect(known.referrerIsa).toBe("PBXResourcesBuildPhase");
     expect(known.property).toBe("files");
+    expect(known.suggestedAction).toBe("removeFromArray");
   });
 
   test("malformed project still parses and serializes", () => {
@@ -365,6 +372,78 @@ describe.skipIf(!native)("native napi", () => {
     expect(project.getObjectProperty(target, "productName")).toBe("CustomProduct");
   });
 
+  test("setObjectValue sets non-string values", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+
+    const target = project.findMainAppTarget("ios");
+    expect(project.setObjectValue(target, "customCount", 3)).toBe(true);
+    expect(project.setObjectValue(target, "customList", ["a", "b"])).toBe(true);
+    expect(project.setObjectValue("000000000000000000000000", "key", 1)).toBe(false);
+
+    const obj = project.toJSON().objects[target];
+    expect(obj.customCount).toBe(3);
+    expect(obj.customList).toEqual(["a", "b"]);
+  });
+
+  test("fullPathOf resolves against setBasePath", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+
+    const appDelegate = "13B07FB01A68108700A75B9A";
+    expect(project.fullPathOf(appDelegate)).toBe("testproject/AppDelegate.m");
+
+    project.setBasePath("/virtual/app");
+    expect(project.getProjectRoot()).toBe("/virtual/app");
+    expect(project.fullPathOf(appDelegate)).toBe("/virtual/app/testproject/AppDelegate.m");
+  });
+
+  test("objectCount matches the isa histogram", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+
+    const histogram = project.isaHistogram();
+    const total = Object.values(histogram).reduce((sum, n) => sum + n, 0);
+    expect(project.objectCount()).toBe(total);
+    expect(project.isEmpty()).toBe(false);
+  });
+
+  test("getResolvedBuildSettings", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+    const target = project.findMainAppTarget("ios");
+
+    project.setBuildSetting(target, "PRODUCT_NAME", "$(TARGET_NAME)");
+    const settings = project.getResolvedBuildSettings(target, "Release");
+    expect(settings.PRODUCT_NAME).toBe(project.getTargetName(target));
+    expect(project.getResolvedBuildSettings(target, "Nope")).toBe(null);
+  });
+
+  test("hasObject", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+
+    expect(project.hasObject(project.mainGroupUuid)).toBe(true);
+    expect(project.hasObject("000000000000000000000000")).toBe(false);
+  });
+
+  test("getBuildPhase / getDefaultConfiguration", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+    const target = project.findMainAppTarget("ios");
+
+    const sources = project.getBuildPhase(target, "PBXSourcesBuildPhase");
+    expect(project.getObjectProperty(sources, "isa")).toBe("PBXSourcesBuildPhase");
+    expect(project.getBuildPhase(target, "PBXHeadersBuildPhase")).toBe(null);
+
+    const configList = project.getObjectProperty(target, "buildConfigurationList");
+    const config = project.getDefaultConfiguration(configList);
+    expect(config.isa).toBe("XCBuildConfiguration");
+    expect(config.name).toBe("Release");
+    expect(config.buildSettings.PRODUCT_NAME).toBe("testproject");
+    expect(project.getDefaultConfiguration("000000000000000000000000")).toBe(null);
+  });
+
   test("findObjectsByIsa", () => {
     const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
     const project = native.XcodeProject.fromString(content);
@@ -379,6 +458,15 @@ describe.skipIf(!native)("native napi", () => {
     expect(fileRefs.length > 0).toBe(true);
   });
 
+  test("isaHistogram counts every object", () => {
+    const content = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = native.XcodeProject.fromString(content);
+
+    const histogram = project.isaHistogram();
+    expect(histogram.PBXProject).toBe(1);
+    expect(histogram.PBXGroup).toBe(project.findObjectsByIsa("PBXGroup").length);
+  });
+
   test("renameTarget cascades through project", () => {
     const tmp = mkdtempSync(join(tmpdir(), "xcode-test-"));
     const pbxpath = join(tmp, "project.pbxproj");
@@ -412,8 +500,10 @@ describe.skipIf(!native)("native napi", () => {
       "com.apple.product-type.app-extension",
       "com.example.widget",
     );
-    const phaseUuid = project.embedExtension(hostTarget, extTarget);
-    expect(phaseUuid).toBeTruthy();
+    const embedding = project.embedExtension(hostTarget, extTarget);
+    expect(project.getObjectProperty(embedding.phaseUuid, "isa")).toBe("PBXCopyFilesBuildPhase");
+    expect(project.getObjectProperty(embedding.buildFileUuid, "isa")).toBe("PBXBuildFile");
+    expect(project.getObjectProperty(embedding.dependencyUuid, "isa")).toBe("PBXTargetDependency");
 
     project.save();
     const output = readFileSync(pbxpath, "utf8");
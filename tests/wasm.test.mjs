@@ -143,8 +143,11 @@ describe.skipIf(!wasm)("wasm", () => {
     expect(ext).toBeTruthy();
 
     project.addDependency(host, ext);
-    const phase = project.embedExtension(host, ext);
-    expect(phase).toBeTruthy();
+    const embedding = project.embedExtension(host, ext);
+    expect(project.getObjectProperty(embedding.phaseUuid, "isa")).toBe("PBXCopyFilesBuildPhase");
+    expect(project.getObjectProperty(embedding.buildFileUuid, "isa")).toBe("PBXBuildFile");
+    expect(project.getObjectProperty(embedding.dependencyUuid, "isa")).toBe("PBXTargetDependency");
+    expect(project.embedExtension(host, "000000000000000000000000")).toBeNull();
 
     const output = project.toBuild();
     expect(output.includes("Widget")).toBe(true);
@@ -178,6 +181,48 @@ describe.skipIf(!wasm)("wasm", () => {
     expect(project.getObjectProperty(target, "productName")).toBe("Custom");
   });
 
+  test("setObjectValue sets non-string values", () => {
+    const text = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = wasm.XcodeProject.fromString(text);
+
+    const target = project.findMainAppTarget("ios");
+    expect(project.setObjectValue(target, "customCount", 3)).toBe(true);
+    expect(project.setObjectValue(target, "customList", ["a", "b"])).toBe(true);
+    expect(project.setObjectValue("000000000000000000000000", "key", 1)).toBe(false);
+
+    const obj = project.toJSON().objects[target];
+    expect(obj.customCount).toBe(3);
+    expect(obj.customList).toEqual(["a", "b"]);
+  });
+
+  test("fullPathOf resolves against setBasePath", () => {
+    const text = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = wasm.XcodeProject.fromString(text);
+
+    const appDelegate = "13B07FB01A68108700A75B9A";
+    expect(project.fullPathOf(appDelegate)).toBe("testproject/AppDelegate.m");
+
+    project.setBasePath("/virtual/app");
+    expect(project.getProjectRoot()).toBe("/virtual/app");
+    expect(project.fullPathOf(appDelegate)).toBe("/virtual/app/testproject/AppDelegate.m");
+  });
+
+  test("objectCount matches the object sections", () => {
+    const text = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = wasm.XcodeProject.fromString(text);
+
+    expect(project.objectCount()).toBe(text.match(/isa = /g).length);
+    expect(project.isEmpty()).toBe(false);
+  });
+
+  test("hasObject", () => {
+    const text = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
+    const project = wasm.XcodeProject.fromString(text);
+
+    expect(project.hasObject(project.mainGroupUuid)).toBe(true);
+    expect(project.hasObject("000000000000000000000000")).toBe(false);
+  });
+
   test("findObjectsByIsa", () => {
     const text = readFileSync(join(FIXTURES_DIR, "project.pbxproj"), "utf8");
     const project = wasm.XcodeProject.fromString(text);